@@ -1,19 +1,44 @@
 use quote::ToTokens;
-use syn::{spanned::Spanned, LitBool, LitInt, Pat, PatType};
+use std::collections::HashSet;
+use syn::{GenericParam, Generics, PatType};
 
-use crate::parsing::{Responses, Security, StrArray};
-
-use self::parsing::PathParam;
+use self::parsing::{
+    combine_errors, doc_iter, with_suggestion, ArgBinding, ArgKind, ConstraintKind, CookieParam,
+    HeaderParam, PathConstraint, PathParam, QueryParam, Responses, Security, StrArray, Warning,
+};
 
 use super::*;
 
 pub struct CompiledRoute {
-    pub method: Method,
+    pub methods: Vec<Method>,
     #[allow(clippy::type_complexity)]
     pub path_params: Vec<(Slash, PathParam)>,
-    pub query_params: Vec<(Ident, Box<Type>)>,
+    pub query_params: Vec<QueryParam>,
+    pub constraints: Vec<PathConstraint>,
+    pub headers: Vec<HeaderParam>,
+    pub cookies: Vec<CookieParam>,
+    pub body: Option<RequestBody>,
+    /// The identifiers that the extracted arguments of the handler are bound to, by their index.
+    pub extracted_args: HashMap<usize, Ident>,
     pub state: Type,
+    /// Whether to generate a function that builds the url of the route.
+    pub url: bool,
+    /// Whether to generate functions that start a request to the route.
+    pub client: bool,
     pub route_lit: LitStr,
+    pub ident: Ident,
+    pub docs: Vec<LitStr>,
+    pub responses: Option<Responses>,
+    pub security: Option<Security>,
+    pub tags: Option<StrArray>,
+    pub warnings: Vec<Warning>,
+}
+
+/// The request body of a route, either declared with `body = <TYPE>` or inferred from the
+/// arguments of the handler.
+pub struct RequestBody {
+    pub ty: Box<Type>,
+    pub declared: bool,
 }
 
 impl CompiledRoute {
@@ -46,67 +71,294 @@ impl CompiledRoute {
     }
 
     /// Removes the arguments in `route` from `args`, and merges them in the output.
+    ///
+    /// Arguments are bound to the parameters of the route by their identifier, or with an
+    /// attribute such as `#[path]`, see [`ArgBinding`]. Query parameters, headers and the body
+    /// declared with an attribute are added to the route.
     pub fn from_route(mut route: Route, function: &ItemFn) -> syn::Result<Self> {
         let sig = &function.sig;
-        let mut arg_map = sig
-            .inputs
-            .iter()
-            .filter_map(|item| match item {
-                syn::FnArg::Receiver(_) => None,
-                syn::FnArg::Typed(pat_type) => Some(pat_type),
-            })
-            .filter_map(|pat_type| match &*pat_type.pat {
-                syn::Pat::Ident(ident) => Some((ident.ident.clone(), pat_type.ty.clone())),
-                _ => None,
-            })
-            .collect::<HashMap<_, _>>();
+        let mut errors = Vec::new();
+        let mut arg_map = HashMap::new();
+        for (i, arg) in sig.inputs.iter().enumerate() {
+            let FnArg::Typed(pat_type) = arg else {
+                continue;
+            };
+            let binding = match ArgBinding::from_arg(pat_type) {
+                Ok(binding) => binding,
+                Err(err) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+            match &binding {
+                Some(ArgBinding::Body) => {
+                    if route.body.replace((*pat_type.ty).clone()).is_some() {
+                        errors.push(syn::Error::new_spanned(
+                            &pat_type.ty,
+                            "the body is declared multiple times",
+                        ));
+                    }
+                    continue;
+                }
+                Some(ArgBinding::Query(name)) => {
+                    let captured = route
+                        .path_params
+                        .iter()
+                        .filter_map(|(_slash, path_param)| path_param.capture())
+                        .any(|(ident, _ty)| ident == name);
+                    if !captured && !route.query_params.iter().any(|param| &param.ident == name) {
+                        route.query_params.push(QueryParam {
+                            ident: name.clone(),
+                            ty: Box::new(parse_quote!(())),
+                            rename: None,
+                            default: None,
+                        });
+                    }
+                }
+                Some(ArgBinding::Header(name, header_name)) => {
+                    match route.headers.iter().find(|header| &header.ident == name) {
+                        Some(header) if header.name.is_some() && header_name.is_some() => {
+                            errors.push(syn::Error::new(
+                                name.span(),
+                                format!("header `{name}` is named in the route already"),
+                            ));
+                        }
+                        Some(_) => {}
+                        None => route.headers.push(HeaderParam {
+                            ident: name.clone(),
+                            ty: Box::new(parse_quote!(())),
+                            name: header_name.clone(),
+                        }),
+                    }
+                }
+                Some(ArgBinding::Path(_)) | None => {}
+            }
+            let (name, kind) = match binding {
+                Some(binding) => {
+                    let kind = binding.kind();
+                    match binding {
+                        ArgBinding::Path(name)
+                        | ArgBinding::Query(name)
+                        | ArgBinding::Header(name, _) => (name, Some(kind)),
+                        ArgBinding::Body => continue,
+                    }
+                }
+                None => match &*pat_type.pat {
+                    syn::Pat::Ident(ident) => (ident.ident.clone(), None),
+                    _ => continue,
+                },
+            };
+            if let Some((_i, _ty, other_kind)) = arg_map.get(&name) {
+                if let Some(kind) = kind.or(*other_kind) {
+                    errors.push(syn::Error::new(
+                        name.span(),
+                        format!(
+                            "{} `{name}` is bound to multiple arguments",
+                            kind.describe()
+                        ),
+                    ));
+                }
+                continue;
+            }
+            arg_map.insert(name, (i, pat_type.ty.clone(), kind));
+        }
+
+        // Every argument that isn't found is reported, with the closest remaining argument
+        let mut extracted_args = HashMap::new();
+        let mut bind = |ident: &Ident, expected: Option<ArgKind>, describe: &str| {
+            let Some((ident, (i, ty, kind))) = arg_map.remove_entry(ident) else {
+                let names = arg_map.keys().map(Ident::to_string).collect::<Vec<_>>();
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    with_suggestion(
+                        format!("{describe} `{ident}` not found in function arguments"),
+                        &ident.to_string(),
+                        names.iter().map(String::as_str),
+                    ),
+                ));
+                return None;
+            };
+            if let Some(kind) = kind.filter(|kind| Some(*kind) != expected) {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "`{ident}` is a {describe}, but the argument is marked with `#[{}]`",
+                        kind.attr_name()
+                    ),
+                ));
+            }
+            extracted_args.insert(i, ident.clone());
+            Some((ident, ty))
+        };
 
         for (_slash, path_param) in &mut route.path_params {
             match path_param {
-                PathParam::Capture(_lit, _, ident, ty, _) => {
-                    let (new_ident, new_ty) = arg_map.remove_entry(ident).ok_or_else(|| {
-                        syn::Error::new(
-                            ident.span(),
-                            format!("path parameter `{}` not found in function arguments", ident),
-                        )
-                    })?;
-                    *ident = new_ident;
-                    *ty = new_ty;
-                }
-                PathParam::WildCard(_lit, _, _star, ident, ty, _) => {
-                    let (new_ident, new_ty) = arg_map.remove_entry(ident).ok_or_else(|| {
-                        syn::Error::new(
-                            ident.span(),
-                            format!("path parameter `{}` not found in function arguments", ident),
-                        )
-                    })?;
-                    *ident = new_ident;
-                    *ty = new_ty;
+                PathParam::Capture(_lit, _, ident, ty, _)
+                | PathParam::WildCard(_lit, _, _, ident, ty, _) => {
+                    if let Some((new_ident, new_ty)) =
+                        bind(ident, Some(ArgKind::Path), "path parameter")
+                    {
+                        *ident = new_ident;
+                        *ty = new_ty;
+                    }
                 }
                 PathParam::Static(_lit) => {}
             }
         }
 
-        let mut query_params = Vec::new();
-        for ident in route.query_params {
-            let (ident, ty) = arg_map.remove_entry(&ident).ok_or_else(|| {
-                syn::Error::new(
-                    ident.span(),
-                    format!(
-                        "query parameter `{}` not found in function arguments",
-                        ident
-                    ),
-                )
-            })?;
-            query_params.push((ident, ty));
+        let mut query_params = route.query_params;
+        for query_param in &mut query_params {
+            if let Some((ident, ty)) =
+                bind(&query_param.ident, Some(ArgKind::Query), "query parameter")
+            {
+                query_param.ident = ident;
+                query_param.ty = ty;
+            }
+        }
+
+        let mut headers = route.headers;
+        for header in &mut headers {
+            if let Some((ident, ty)) = bind(&header.ident, Some(ArgKind::Header), "header") {
+                header.ident = ident;
+                header.ty = ty;
+            }
+        }
+
+        let mut cookies = route.cookies;
+        for cookie in &mut cookies {
+            if let Some((ident, ty)) = bind(&cookie.ident, None, "cookie") {
+                cookie.ident = ident;
+                cookie.ty = ty;
+            }
         }
 
+        // Query parameters and headers are declared by their arguments, but path parameters must
+        // be captured by the path
+        for (name, (_i, _ty, kind)) in &arg_map {
+            let Some(ArgKind::Path) = kind else {
+                continue;
+            };
+            let names = route
+                .path_params
+                .iter()
+                .filter_map(|(_slash, path_param)| path_param.capture())
+                .map(|(ident, _ty)| ident.to_string())
+                .collect::<Vec<_>>();
+            errors.push(syn::Error::new(
+                name.span(),
+                with_suggestion(
+                    format!("path parameter `{name}` not found in the route"),
+                    &name.to_string(),
+                    names.iter().map(String::as_str),
+                ),
+            ));
+        }
+        combine_errors(errors)?;
+
+        let remaining_args = sig
+            .inputs
+            .iter()
+            .enumerate()
+            .filter(|(i, _arg)| !extracted_args.contains_key(i))
+            .filter_map(|(_i, arg)| match arg {
+                syn::FnArg::Receiver(_) => None,
+                syn::FnArg::Typed(pat_type) => Some(pat_type),
+            })
+            .collect::<Vec<_>>();
+        let body = find_body(route.body, &remaining_args)?;
+
         Ok(Self {
             route_lit: route.route_lit,
-            method: route.method,
+            methods: route.methods,
             path_params: route.path_params,
             query_params,
+            constraints: route.constraints,
+            headers,
+            cookies,
+            body,
+            extracted_args,
             state: route.state.unwrap_or_else(|| guess_state_type(sig)),
+            url: route.url,
+            client: route.client,
+            ident: sig.ident.clone(),
+            docs: doc_iter(&function.attrs).cloned().collect(),
+            responses: route.responses,
+            security: route.security,
+            tags: route.tags,
+            warnings: route.warnings,
+        })
+    }
+
+    pub fn constraints_extractor(&self) -> Option<TokenStream2> {
+        if self.constraints.is_empty() {
+            return None;
+        }
+
+        Some(quote! {
+            _: __PathConstraints__,
+        })
+    }
+
+    /// Generates `__PathConstraints__`, which checks the raw path captures against their
+    /// constraints, and responds with `404 Not Found` when a capture doesn't satisfy one.
+    ///
+    /// It must be the first extractor of the handler, so that the captures are checked before
+    /// the `Path` extractor parses them.
+    pub fn constraints_struct(&self) -> Option<TokenStream2> {
+        if self.constraints.is_empty() {
+            return None;
+        }
+
+        let private = quote! { ::axum_controller::__private };
+        let checks = self.constraints.iter().map(|constraint| {
+            let name = constraint.ident.to_string();
+            let check = match &constraint.kind {
+                ConstraintKind::Type(ty) => quote! {
+                    value.parse::<#ty>().is_ok()
+                },
+                ConstraintKind::Range(range) => quote! {
+                    value.parse::<i128>().is_ok_and(|value| (#range).contains(&value))
+                },
+                ConstraintKind::Regex(pattern) => quote! {{
+                    static REGEX: #private::PathRegex = #private::PathRegex::new(#pattern);
+                    REGEX.is_match(value)
+                }},
+            };
+            quote! { #name => #check, }
+        });
+
+        Some(quote! {
+            struct __PathConstraints__;
+
+            impl<S: ::std::marker::Send + ::std::marker::Sync> ::axum::extract::FromRequestParts<S>
+                for __PathConstraints__
+            {
+                type Rejection = ::axum::response::Response;
+
+                async fn from_request_parts(
+                    parts: &mut ::axum::http::request::Parts,
+                    state: &S,
+                ) -> ::std::result::Result<Self, Self::Rejection> {
+                    let params =
+                        <::axum::extract::RawPathParams as ::axum::extract::FromRequestParts<S>>::from_request_parts(parts, state)
+                            .await
+                            .map_err(::axum::response::IntoResponse::into_response)?;
+                    for (name, value) in &params {
+                        let satisfied = match name {
+                            #(#checks)*
+                            _ => true,
+                        };
+                        if !satisfied {
+                            return ::std::result::Result::Err(
+                                ::axum::response::IntoResponse::into_response(
+                                    ::axum::http::StatusCode::NOT_FOUND,
+                                ),
+                            );
+                        }
+                    }
+                    ::std::result::Result::Ok(Self)
+                }
+            }
         })
     }
 
@@ -131,49 +383,128 @@ impl CompiledRoute {
             return None;
         }
 
-        let idents = self.query_params.iter().map(|item| &item.0);
+        // axum's `Query` can't deserialize repeated keys into a collection, axum-extra's can
+        let query = match self
+            .query_params
+            .iter()
+            .any(|query_param| is_collection(&query_param.ty))
+        {
+            true => quote! { ::axum_controller::__private::axum_extra::extract::Query },
+            false => quote! { ::axum::extract::Query },
+        };
+        let idents = self
+            .query_params
+            .iter()
+            .map(|query_param| &query_param.ident);
         Some(quote! {
-            ::axum::extract::Query(__QueryParams__ {
+            #query(__QueryParams__ {
                 #(#idents,)*
-            }): ::axum::extract::Query<__QueryParams__>,
+            }): #query<__QueryParams__>,
         })
     }
 
-    pub fn query_params_struct(&self ) -> Option<TokenStream2> {
-        match self.query_params.is_empty() {
-            true => None,
-            false => {
-                let idents = self.query_params.iter().map(|item| &item.0);
-                let types = self.query_params.iter().map(|item| &item.1);
-                let derive =  quote! { #[derive(::serde::Deserialize)] };
-                Some(quote! {
-                    #derive
-                    struct __QueryParams__ {
-                        #(#idents: #types,)*
-                    }
-                })
-            }
+    pub fn query_params_struct(&self) -> Option<TokenStream2> {
+        if self.query_params.is_empty() {
+            return None;
         }
-    }
 
-    pub fn extracted_idents(&self) -> Vec<Ident> {
-        let mut idents = Vec::new();
-        for (_slash, path_param) in &self.path_params {
-            if let Some((ident, _ty)) = path_param.capture() {
-                idents.push(ident.clone());
+        let mut default_fns = Vec::new();
+        let fields = self.query_params.iter().map(|query_param| {
+            let QueryParam {
+                ident,
+                ty,
+                rename,
+                default,
+            } = query_param;
+            let rename = rename
+                .as_ref()
+                .map(|rename| quote! { #[serde(rename = #rename)] });
+            let default = match default {
+                Some(default) => {
+                    let default_fn = format_ident!("__default_{}__", ident);
+                    default_fns.push(quote! {
+                        fn #default_fn() -> #ty {
+                            #default
+                        }
+                    });
+                    let default_fn = format!("__QueryParams__::{default_fn}");
+                    Some(quote! { #[serde(default = #default_fn)] })
+                }
+                // A collection is empty (or `None`) when its key is missing from the query
+                None if is_collection(ty) => Some(quote! { #[serde(default)] }),
+                None => None,
+            };
+            quote! {
+                #rename
+                #default
+                #ident: #ty,
             }
-            // if let Some((_colon, ident, _ty)) = colon {
-            //     idents.push(ident.clone());
-            // }
-        }
-        for (ident, _ty) in &self.query_params {
-            idents.push(ident.clone());
-        }
-        idents
+        });
+        let fields = fields.collect::<Vec<_>>();
+
+        // serde is re-exported, so that users don't need to depend on it
+        let derive = quote! {
+            #[derive(::axum_controller::__private::serde::Deserialize)]
+            #[serde(crate = "::axum_controller::__private::serde")]
+        };
+        Some(quote! {
+            #derive
+            struct __QueryParams__ {
+                #(#fields)*
+            }
+
+            impl __QueryParams__ {
+                #(#default_fns)*
+            }
+        })
+    }
+
+    pub fn header_extractor(&self) -> Option<TokenStream2> {
+        let idents = self.headers.iter().map(|header| &header.ident);
+        params_extractor(format_ident!("__HeaderParams__"), idents)
+    }
+
+    /// Generates `__HeaderParams__`, which extracts the headers of the route, and responds with
+    /// `400 Bad Request` when a header is missing or can't be parsed.
+    pub fn header_params_struct(&self) -> Option<TokenStream2> {
+        let params = self
+            .headers
+            .iter()
+            .map(|header| (&header.ident, &*header.ty, header.name()));
+        params_struct(
+            format_ident!("__HeaderParams__"),
+            params,
+            quote! { &parts.headers },
+        )
+    }
+
+    pub fn cookie_extractor(&self) -> Option<TokenStream2> {
+        let idents = self.cookies.iter().map(|cookie| &cookie.ident);
+        params_extractor(format_ident!("__CookieParams__"), idents)
+    }
+
+    /// Generates `__CookieParams__`, which extracts the cookies of the route from a `CookieJar`,
+    /// and responds with `400 Bad Request` when a cookie is missing or can't be parsed.
+    pub fn cookie_params_struct(&self) -> Option<TokenStream2> {
+        let params = self
+            .cookies
+            .iter()
+            .map(|cookie| (&cookie.ident, &*cookie.ty, cookie.name()));
+        params_struct(
+            format_ident!("__CookieParams__"),
+            params,
+            quote! {
+                &::axum_controller::__private::axum_extra::extract::CookieJar::from_headers(
+                    &parts.headers,
+                )
+            },
+        )
     }
 
     /// The arguments not used in the route.
     /// Map the identifier to `___arg___{i}: Type`.
+    ///
+    /// A `self` receiver is extracted from the state, as `State(___arg___{i}): State<Self>`.
     pub fn remaining_pattypes_numbered(
         &self,
         args: &Punctuated<FnArg, Comma>,
@@ -181,51 +512,965 @@ impl CompiledRoute {
         args.iter()
             .enumerate()
             .filter_map(|(i, item)| {
-                if let FnArg::Typed(pat_type) = item {
-                    if let syn::Pat::Ident(pat_ident) = &*pat_type.pat {
-                        if self.path_params.iter().any(|(_slash, path_param)| {
-                            if let Some((path_ident, _ty)) = path_param.capture() {
-                                path_ident == &pat_ident.ident
-                            } else {
-                                false
+                let ident = format_ident!("___arg___{}", i);
+                match item {
+                    FnArg::Typed(_) if self.extracted_args.contains_key(&i) => None,
+                    FnArg::Typed(pat_type) => {
+                        let mut new_pat_type = pat_type.clone();
+                        new_pat_type.pat = Box::new(parse_quote!(#ident));
+                        Some(new_pat_type)
+                    }
+                    FnArg::Receiver(receiver) => {
+                        let ty = match &*receiver.ty {
+                            Type::Reference(reference) if receiver.reference.is_some() => {
+                                &reference.elem
                             }
-                        }) || self
-                            .query_params
-                            .iter()
-                            .any(|(query_ident, _)| query_ident == &pat_ident.ident)
-                        {
-                            return None;
-                        }
+                            ty => ty,
+                        };
+                        let mutability = &receiver.mutability;
+                        let FnArg::Typed(pat_type) = parse_quote!(
+                            ::axum::extract::State(#mutability #ident): ::axum::extract::State<#ty>
+                        ) else {
+                            unreachable!()
+                        };
+                        Some(pat_type)
                     }
+                }
+            })
+            .collect()
+    }
 
-                    let mut new_pat_type = pat_type.clone();
-                    let ident = format_ident!("___arg___{}", i);
-                    new_pat_type.pat = Box::new(parse_quote!(#ident));
-                    Some(new_pat_type)
-                } else {
-                    unimplemented!("Self type is not supported")
+    /// The arguments to call the handler with, in the order of `args`.
+    ///
+    /// Path- and query-parameters are passed by their identifier, the other arguments are
+    /// passed as `___arg___{i}`, matching [`Self::remaining_pattypes_numbered`].
+    pub fn call_args(&self, args: &Punctuated<FnArg, Comma>) -> Vec<TokenStream2> {
+        args.iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let ident = format_ident!("___arg___{}", i);
+                match item {
+                    FnArg::Typed(_) => match self.extracted_args.get(&i) {
+                        Some(ident) => quote!(#ident),
+                        None => quote!(#ident),
+                    },
+                    FnArg::Receiver(receiver) => {
+                        match (&receiver.reference, &receiver.mutability) {
+                            (Some(_), Some(_)) => quote!(&mut #ident),
+                            (Some(_), None) => quote!(&#ident),
+                            (None, _) => quote!(#ident),
+                        }
+                    }
                 }
             })
             .collect()
     }
 
+    /// Generates the `MethodRouter` that routes the methods of this route to `handler`.
+    ///
+    /// A single method uses its axum routing function, such as `get`, and multiple methods
+    /// are combined with `on`. Custom methods are routed by the fallback of the method router,
+    /// guarded by a layer that responds with `405 Method Not Allowed` to every method that
+    /// isn't listed, and lets `HEAD` through if `GET` is listed, as axum does.
+    pub fn to_method_router(&self, handler: TokenStream2) -> TokenStream2 {
+        let filters = self
+            .methods
+            .iter()
+            .filter_map(Method::to_method_filter)
+            .collect::<Vec<_>>();
+        let has_custom = filters.len() < self.methods.len();
+
+        if !has_custom && self.methods.len() == 1 {
+            let http_method = self.methods[0].to_axum_method_name();
+            let http_method = http_method.expect("standard methods have a routing function");
+            return quote! { ::axum::routing::#http_method(#handler) };
+        }
+        let method_router = match filters.split_first() {
+            Some((first, rest)) => quote! { ::axum::routing::on(#first #(.or(#rest))*, #handler) },
+            None => quote! { ::axum::routing::MethodRouter::new() },
+        };
+        if !has_custom {
+            return method_router;
+        }
+
+        let mut methods = self
+            .methods
+            .iter()
+            .map(Method::as_http_str)
+            .collect::<Vec<_>>();
+        if methods.iter().any(|method| method == "GET")
+            && !methods.iter().any(|method| method == "HEAD")
+        {
+            methods.push("HEAD".to_string());
+        }
+        let allow = methods.join(",");
+        quote! {
+            #method_router.fallback(#handler).layer(::axum::middleware::from_fn(
+                |request: ::axum::extract::Request, next: ::axum::middleware::Next| async move {
+                    if matches!(request.method().as_str(), #(#methods)|*) {
+                        next.run(request).await
+                    } else {
+                        ::axum::response::IntoResponse::into_response((
+                            ::axum::http::StatusCode::METHOD_NOT_ALLOWED,
+                            [(::axum::http::header::ALLOW, #allow)],
+                        ))
+                    }
+                },
+            ))
+        }
+    }
+
+    /// Generates a function `#vis fn #ident(<path params>, <query params>) -> String`, which
+    /// builds the url of this route, prefixed with `base`.
+    ///
+    /// Path- and query-parameters are percent-encoded, and optional query-parameters are
+    /// left out when they are `None`. The function has the parameters of `generics` that the
+    /// types of the parameters use.
+    pub fn to_url_fn(
+        &self,
+        ident: &Ident,
+        vis: &syn::Visibility,
+        base: &str,
+        generics: &Generics,
+    ) -> TokenStream2 {
+        let params = self.url_params();
+        let url = self.url_expr(base);
+        let types = self.url_param_types().map(|(_ident, ty)| ty);
+        let generics = self.used_generics(generics, types);
+        let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+
+        let doc = format!(
+            "Builds the url of the `{}` route, with the given path- and query-parameters.",
+            self.ident
+        );
+        quote! {
+            #[doc = #doc]
+            #vis fn #ident #impl_generics(#(#params),*) -> ::std::string::String #where_clause {
+                #url
+            }
+        }
+    }
+
+    /// Generates a function `#vis fn #ident(client: &Client, <path params>, <query params>,
+    /// <headers>, <cookies>[, body]) -> RequestBuilder`, which starts a `reqwest` request to
+    /// this route, prefixed with `base`. The body is a parameter if the route has a body that a
+    /// client can serialize, see [`Self::client_body`].
+    ///
+    /// Routes with multiple methods get a function per method instead, named `#ident_<method>`.
+    pub fn to_request_fns(
+        &self,
+        ident: &Ident,
+        vis: &syn::Visibility,
+        base: &str,
+        generics: &Generics,
+    ) -> TokenStream2 {
+        let client = quote! { ::axum_controller::client };
+        let url = self.url_expr(base);
+        let body = self.client_body();
+        let param_types = self
+            .url_param_types()
+            .chain(
+                self.headers
+                    .iter()
+                    .map(|header| (&header.ident, &*header.ty)),
+            )
+            .chain(
+                self.cookies
+                    .iter()
+                    .map(|cookie| (&cookie.ident, &*cookie.ty)),
+            )
+            .collect::<Vec<_>>();
+        let params = param_types
+            .iter()
+            .map(|(ident, ty)| quote! { #ident: #ty })
+            .chain(body.as_ref().map(|(ty, _call)| quote! { body: #ty }))
+            .collect::<Vec<_>>();
+        let types = param_types.iter().map(|(_ident, ty)| *ty);
+        let generics = self.used_generics(generics, types.chain(body.as_ref().map(|(ty, _)| ty)));
+        let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+
+        let headers = self.headers.iter().map(|header| {
+            let ident = &header.ident;
+            let name = header.name();
+            match is_option(&header.ty) {
+                true => quote! {
+                    if let ::std::option::Option::Some(value) = &#ident {
+                        __request__ = __request__.header(#name, value.to_string());
+                    }
+                },
+                false => quote! { __request__ = __request__.header(#name, #ident.to_string()); },
+            }
+        });
+        // All cookies are sent in a single `Cookie` header
+        let cookies = (!self.cookies.is_empty()).then(|| {
+            let cookies = self.cookies.iter().map(|cookie| {
+                let ident = &cookie.ident;
+                let name = cookie.name();
+                match is_option(&cookie.ty) {
+                    true => quote! {
+                        if let ::std::option::Option::Some(value) = &#ident {
+                            __cookies__.push(::std::format!("{}={}", #name, value));
+                        }
+                    },
+                    false => quote! { __cookies__.push(::std::format!("{}={}", #name, #ident)); },
+                }
+            });
+            quote! {
+                let mut __cookies__ = ::std::vec::Vec::<::std::string::String>::new();
+                #(#cookies)*
+                if !__cookies__.is_empty() {
+                    __request__ = __request__.header(
+                        #client::reqwest::header::COOKIE,
+                        __cookies__.join("; "),
+                    );
+                }
+            }
+        });
+        let headers = headers.collect::<Vec<_>>();
+        let body_call = body.as_ref().map(|(_ty, call)| call);
+
+        let fns = self.methods.iter().map(|method| {
+            let http_method = method.as_http_str();
+            let ident = match self.methods.len() {
+                1 => ident.clone(),
+                // Custom methods, such as `M-SEARCH`, aren't always identifiers
+                _ => format_ident!(
+                    "{ident}_{}",
+                    http_method
+                        .to_lowercase()
+                        .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+                ),
+            };
+            let method = match method {
+                Method::Custom(_) => quote! {
+                    #client::reqwest::Method::from_bytes(#http_method.as_bytes())
+                        .expect("custom methods are valid http methods")
+                },
+                _ => {
+                    let method = Ident::new(&http_method, method.span());
+                    quote! { #client::reqwest::Method::#method }
+                }
+            };
+
+            let doc = format!(
+                "Starts a `{http_method}` request to the `{}` route, with the given path- and \
+                 query-parameters, headers, cookies{}.",
+                self.ident,
+                if body.is_some() { " and body" } else { "" },
+            );
+            quote! {
+                #[doc = #doc]
+                #vis fn #ident #impl_generics(
+                    client: &#client::Client,
+                    #(#params),*
+                ) -> #client::reqwest::RequestBuilder #where_clause {
+                    let mut __request__ = client.request(#method, &#url);
+                    #(#headers)*
+                    #cookies
+                    __request__ #body_call
+                }
+            }
+        });
+        quote! { #(#fns)* }
+    }
+
+    /// The type of the `body` parameter of a request function, and the `RequestBuilder` call
+    /// that sets it, if the body of this route is `Json<T>`, `Form<T>` or `String`. Other bodies
+    /// are left to the caller.
+    fn client_body(&self) -> Option<(Type, TokenStream2)> {
+        let body = self.body.as_ref()?;
+        let Type::Path(path) = &*body.ty else {
+            return None;
+        };
+        let ty = body_schema_type(&body.ty)?;
+        match path.path.segments.last()?.ident.to_string().as_str() {
+            "Json" => Some((parse_quote!(&#ty), quote! { .json(body) })),
+            "Form" => Some((parse_quote!(&#ty), quote! { .form(body) })),
+            "String" => Some((parse_quote!(#ty), quote! { .body(body) })),
+            _ => None,
+        }
+    }
+
+    /// The parameters of the url of this route: the path-parameters followed by the
+    /// query-parameters.
+    fn url_params(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        self.url_param_types()
+            .map(|(ident, ty)| quote! { #ident: #ty })
+    }
+
+    fn url_param_types(&self) -> impl Iterator<Item = (&Ident, &Type)> + '_ {
+        self.path_params
+            .iter()
+            .filter_map(|(_slash, path_param)| path_param.capture())
+            .chain(
+                self.query_params
+                    .iter()
+                    .map(|query_param| (&query_param.ident, &*query_param.ty)),
+            )
+    }
+
+    /// The parameters of `generics` that `types` use, such as the types of the url parameters,
+    /// together with the parameters their bounds use. The other parameters can't be inferred
+    /// from the parameters of a function, so they are left out.
+    fn used_generics<'a>(
+        &self,
+        generics: &Generics,
+        types: impl Iterator<Item = &'a Type>,
+    ) -> Generics {
+        let names = generics
+            .params
+            .iter()
+            .map(|param| match param {
+                GenericParam::Type(param) => param.ident.to_string(),
+                GenericParam::Lifetime(param) => param.lifetime.ident.to_string(),
+                GenericParam::Const(param) => param.ident.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let predicates = generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+            .collect::<Vec<_>>();
+
+        let mut used = HashSet::new();
+        for ty in types {
+            collect_idents(ty.to_token_stream(), &mut used);
+        }
+        // Bounds can use other parameters, which are added until none are left
+        loop {
+            let len = used.len();
+            for (param, name) in generics.params.iter().zip(&names) {
+                if used.contains(name) {
+                    collect_idents(param.to_token_stream(), &mut used);
+                }
+            }
+            for predicate in &predicates {
+                let mut idents = HashSet::new();
+                collect_idents(predicate.to_token_stream(), &mut idents);
+                if names
+                    .iter()
+                    .any(|name| used.contains(name) && idents.contains(name))
+                {
+                    used.extend(idents);
+                }
+            }
+            if used.len() == len {
+                break;
+            }
+        }
+
+        let params = generics
+            .params
+            .iter()
+            .zip(&names)
+            .filter(|(_param, name)| used.contains(*name))
+            .map(|(param, _name)| param);
+        let predicates = predicates.into_iter().filter(|predicate| {
+            let mut idents = HashSet::new();
+            collect_idents(predicate.to_token_stream(), &mut idents);
+            names
+                .iter()
+                .all(|name| used.contains(name) || !idents.contains(name))
+        });
+        let mut used_generics: Generics = parse_quote!(<#(#params),*>);
+        used_generics.where_clause = Some(parse_quote!(where #(#predicates,)*));
+        used_generics
+    }
+
+    /// Generates a block evaluating to the url of this route, built from the parameters in
+    /// [`Self::url_params`].
+    fn url_expr(&self, base: &str) -> TokenStream2 {
+        let private = quote! { ::axum_controller::__private };
+        let base = base.trim_end_matches('/');
+
+        // A route at `/` is mounted at the base path itself, so that `/users` + `/` is `/users`
+        let is_root = !base.is_empty()
+            && matches!(
+                self.path_params.as_slice(),
+                [(_slash, PathParam::Static(lit))] if lit.value().is_empty()
+            );
+        let segments =
+            self.path_params.iter().filter(|_| !is_root).map(
+                |(_slash, path_param)| match path_param {
+                    PathParam::Capture(_lit, _, ident, _, _) => {
+                        quote! { #private::push_path_segment(&mut __url__, &#ident); }
+                    }
+                    PathParam::WildCard(_lit, _, _star, ident, _, _) => {
+                        quote! { #private::push_wildcard(&mut __url__, &#ident); }
+                    }
+                    PathParam::Static(lit) => {
+                        let segment = format!("/{}", lit.value());
+                        quote! { __url__.push_str(#segment); }
+                    }
+                },
+            );
+        let query_params = self.query_params.iter().map(|query_param| {
+            let ident = &query_param.ident;
+            let name = query_param.name();
+            match (is_option(&query_param.ty), is_collection(&query_param.ty)) {
+                (true, true) => quote! {
+                    for value in #ident.iter().flatten() {
+                        #private::push_query_param(&mut __url__, #name, value);
+                    }
+                },
+                (true, false) => quote! {
+                    if let Some(value) = &#ident {
+                        #private::push_query_param(&mut __url__, #name, value);
+                    }
+                },
+                (false, true) => quote! {
+                    for value in &#ident {
+                        #private::push_query_param(&mut __url__, #name, value);
+                    }
+                },
+                (false, false) => {
+                    quote! { #private::push_query_param(&mut __url__, #name, &#ident); }
+                }
+            }
+        });
+
+        quote! {
+            {
+                let mut __url__ = ::std::string::String::from(#base);
+                #(#segments)*
+                #(#query_params)*
+                __url__
+            }
+        }
+    }
+
+    /// Generates a constant expression evaluating to the `axum_controller::RouteInfo` of this
+    /// route, which is served at `axum_path` by the handler named `handler`, defined in the
+    /// module `module_path`.
+    pub fn to_route_info(
+        &self,
+        axum_path: &str,
+        handler: &str,
+        module_path: TokenStream2,
+    ) -> TokenStream2 {
+        let methods = self.methods.iter().map(Method::as_http_str);
+        let path = self.route_lit.value();
+        let captures = self
+            .path_params
+            .iter()
+            .filter_map(|(_slash, path_param)| path_param.capture())
+            .map(|(ident, ty)| param_info(&ident.to_string(), ty));
+        let query_params = self
+            .query_params
+            .iter()
+            .map(|query_param| param_info(&query_param.name(), &query_param.ty));
+        let headers = self
+            .headers
+            .iter()
+            .map(|header| param_info(&header.name(), &header.ty));
+        let cookies = self
+            .cookies
+            .iter()
+            .map(|cookie| param_info(&cookie.name(), &cookie.ty));
+        let docs = self
+            .docs
+            .iter()
+            .map(|doc| {
+                let doc = doc.value();
+                doc.strip_prefix(' ').unwrap_or(&doc).to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let body = match &self.body {
+            Some(body) => {
+                let ty = type_string(&body.ty);
+                let content_type = match body_content_type(&body.ty) {
+                    Some(content_type) => quote!(Some(#content_type)),
+                    None => quote!(None),
+                };
+                quote! {
+                    Some(::axum_controller::BodyInfo {
+                        ty: #ty,
+                        content_type: #content_type,
+                    })
+                }
+            }
+            None => quote!(None),
+        };
+        let state = type_string(&self.state);
+
+        quote! {
+            ::axum_controller::RouteInfo {
+                methods: &[#(#methods),*],
+                path: #path,
+                axum_path: #axum_path,
+                captures: &[#(#captures),*],
+                query_params: &[#(#query_params),*],
+                headers: &[#(#headers),*],
+                cookies: &[#(#cookies),*],
+                body: #body,
+                handler: #handler,
+                module_path: #module_path,
+                docs: #docs,
+                state: #state,
+            }
+        }
+    }
+
+    /// Generates an expression evaluating to the OpenAPI documentation of this route, as
+    /// `(Vec<(String, RefOr<Schema>)>, Paths)`, the first two elements of utoipa-axum's
+    /// `UtoipaMethodRouter`.
+    ///
+    /// `path` is the axum path the route is served at. Methods that OpenAPI doesn't support,
+    /// such as `CONNECT`, are left out of the documentation.
+    pub fn to_openapi(&self, path: &str) -> TokenStream2 {
+        let utoipa = quote! { ::axum_controller::openapi::utoipa };
+        let operation_id = self.ident.to_string();
+
+        let mut lines = self
+            .docs
+            .iter()
+            .map(|doc| doc.value().trim().to_string())
+            .skip_while(|line| line.is_empty());
+        let summary = lines.next();
+        let description = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+        let summary =
+            summary.map_or_else(|| quote!(None::<&str>), |summary| quote!(Some(#summary)));
+        let description = match description.is_empty() {
+            true => quote!(None::<&str>),
+            false => quote!(Some(#description)),
+        };
+
+        let tags = self.tags.iter().flat_map(|tags| &tags.0);
+
+        let path_params = self
+            .path_params
+            .iter()
+            .filter_map(|(_slash, path_param)| path_param.capture())
+            .map(|(ident, ty)| openapi_parameter(ident.to_string(), "Path", true, ty));
+        let query_params = self.query_params.iter().map(|query_param| {
+            let required = !query_param.is_optional();
+            openapi_parameter(query_param.name(), "Query", required, &query_param.ty)
+        });
+        let headers = self.headers.iter().map(|header| {
+            openapi_parameter(header.name(), "Header", !is_option(&header.ty), &header.ty)
+        });
+        let cookies = self.cookies.iter().map(|cookie| {
+            openapi_parameter(cookie.name(), "Cookie", !is_option(&cookie.ty), &cookie.ty)
+        });
+        let parameters = path_params
+            .chain(query_params)
+            .chain(headers)
+            .chain(cookies);
+
+        // Only declared bodies are documented with a schema, as it requires the type to implement
+        // `ToSchema`.
+        let body_schema = self
+            .body
+            .iter()
+            .filter(|body| body.declared)
+            .find_map(|body| body_schema_type(&body.ty));
+        let request_body = self.body.as_ref().and_then(|body| {
+            let content_type = body_content_type(&body.ty)?;
+            let schema = body_schema
+                .map(|ty| quote!(.schema(Some(<#ty as #utoipa::PartialSchema>::schema()))));
+            Some(quote! {
+                .request_body(Some(
+                    #utoipa::openapi::request_body::RequestBodyBuilder::new()
+                        .content(
+                            #content_type,
+                            #utoipa::openapi::ContentBuilder::new()#schema.build(),
+                        )
+                        .required(Some(#utoipa::openapi::Required::True))
+                        .build(),
+                ))
+            })
+        });
+
+        let responses = self.responses.iter().flat_map(|responses| &responses.0);
+        let response_types = responses.clone().map(|(_status, ty)| ty);
+        let responses = responses.map(|(status, ty)| {
+            let code = status.to_string();
+            quote! {
+                .response(
+                    #code,
+                    #utoipa::openapi::ResponseBuilder::new()
+                        .description(
+                            ::axum::http::StatusCode::from_u16(#status)
+                                .ok()
+                                .and_then(|status| status.canonical_reason())
+                                .unwrap_or_default(),
+                        )
+                        .content(
+                            "application/json",
+                            #utoipa::openapi::ContentBuilder::new()
+                                .schema(Some(<#ty as #utoipa::PartialSchema>::schema()))
+                                .build(),
+                        )
+                        .build(),
+                )
+            }
+        });
+        // OpenAPI requires at least one response
+        let responses = match self.responses.is_some() {
+            true => quote!(#(#responses)*),
+            false => quote!(.response("200", #utoipa::openapi::Response::new("OK"))),
+        };
+
+        let securities = self.security.iter().flat_map(|security| &security.0);
+        let securities = securities.map(|(scheme, scopes)| {
+            let scopes = &scopes.0;
+            quote! {
+                #utoipa::openapi::security::SecurityRequirement::new(
+                    #scheme,
+                    ::std::vec::Vec::<&str>::from([#(#scopes),*]),
+                )
+            }
+        });
+
+        let http_methods = self
+            .methods
+            .iter()
+            .filter_map(Method::to_openapi_method)
+            .collect::<Vec<_>>();
+        // A route with only undocumented methods, such as `CONNECT`, has no path item
+        let path = (!http_methods.is_empty()).then(|| {
+            quote! {
+                .path(
+                    #path,
+                    #utoipa::openapi::path::PathItem::from_http_methods(
+                        [#(#http_methods),*],
+                        operation,
+                    ),
+                )
+            }
+        });
+        let body_schemas = body_schema.iter();
+
+        quote! {
+            {
+                let mut schemas = ::std::vec::Vec::new();
+                #(<#body_schemas as #utoipa::ToSchema>::schemas(&mut schemas);)*
+                #(<#response_types as #utoipa::ToSchema>::schemas(&mut schemas);)*
+
+                #[allow(unused_variables)]
+                let operation = #utoipa::openapi::path::OperationBuilder::new()
+                    .operation_id(Some(#operation_id))
+                    .summary(#summary)
+                    .description(#description)
+                    #(.tag(#tags))*
+                    #(.parameter(#parameters))*
+                    #request_body
+                    .responses(#utoipa::openapi::ResponsesBuilder::new()#responses.build())
+                    #(.security(#securities))*
+                    .build();
+
+                let paths = #utoipa::openapi::path::PathsBuilder::new()
+                    #path
+                    .build();
+
+                (schemas, paths)
+            }
+        }
+    }
+
+    /// Whether any method of this route can be documented in OpenAPI, which excludes `CONNECT`
+    /// and custom methods.
+    pub fn is_documentable(&self) -> bool {
+        self.methods
+            .iter()
+            .any(|method| method.to_openapi_method().is_some())
+    }
+
     pub(crate) fn to_doc_comments(&self) -> TokenStream2 {
         let mut doc = format!(
             "# Handler information
 - Method: `{}`
 - Path: `{}`
 - State: `{}`",
-            self.method.to_axum_method_name(),
+            self.methods
+                .iter()
+                .map(|method| match method.to_axum_method_name() {
+                    Some(name) => name.to_string(),
+                    None => method.as_http_str(),
+                })
+                .collect::<Vec<_>>()
+                .join(" | "),
             self.route_lit.value(),
             self.state.to_token_stream(),
         );
 
+        if let Some(body) = &self.body {
+            doc.push_str(&format!("\n- Body: `{}`", type_string(&body.ty)));
+        }
+        if let Some(tags) = &self.tags {
+            doc.push_str(&format!("\n- Tags: `{tags}`"));
+        }
+        if let Some(responses) = &self.responses {
+            doc.push_str(&format!("\n- Responses: `{responses}`"));
+        }
+        if let Some(security) = &self.security {
+            doc.push_str(&format!("\n- Security: `{security}`"));
+        }
+
         quote!(
             #[doc = #doc]
         )
     }
 }
 
+/// Whether the last segment of `ty` is `Option`.
+pub fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Whether `ty` is a collection that is deserialized from repeated query keys, such as
+/// `Vec<T>` or `HashSet<T>`, or an `Option` of one.
+pub fn is_collection(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(last_segment) = path.path.segments.last() else {
+        return false;
+    };
+    match last_segment.ident.to_string().as_str() {
+        "Vec" | "HashSet" | "BTreeSet" => true,
+        "Option" => match &last_segment.arguments {
+            PathArguments::AngleBracketed(args) => {
+                matches!(args.args.first(), Some(GenericArgument::Type(ty)) if is_collection(ty))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Destructures the struct `name` generated by [`params_struct`] into `idents`, or `None` if
+/// there are no parameters.
+fn params_extractor<'a>(
+    name: Ident,
+    idents: impl ExactSizeIterator<Item = &'a Ident>,
+) -> Option<TokenStream2> {
+    if idents.len() == 0 {
+        return None;
+    }
+
+    Some(quote! {
+        #name {
+            #(#idents,)*
+        }: #name,
+    })
+}
+
+/// Generates the struct `name`, which extracts each of `params` (its ident, type and name)
+/// from the `__private::ParamSource` that `source` evaluates to, or `None` if there are no
+/// parameters. `source` can use the `parts` of the request.
+fn params_struct<'a>(
+    name: Ident,
+    params: impl ExactSizeIterator<Item = (&'a Ident, &'a Type, String)>,
+    source: TokenStream2,
+) -> Option<TokenStream2> {
+    if params.len() == 0 {
+        return None;
+    }
+
+    let private = quote! { ::axum_controller::__private };
+    let (fields, values): (Vec<_>, Vec<_>) = params
+        .map(|(ident, ty, name)| {
+            let value = match is_option(ty) {
+                true => quote! { #private::optional_param(source, #name)? },
+                false => quote! { #private::required_param(source, #name)? },
+            };
+            (quote! { #ident: #ty, }, quote! { #ident: #value, })
+        })
+        .unzip();
+
+    Some(quote! {
+        struct #name {
+            #(#fields)*
+        }
+
+        impl<S: ::std::marker::Send + ::std::marker::Sync> ::axum::extract::FromRequestParts<S>
+            for #name
+        {
+            type Rejection = (::axum::http::StatusCode, ::std::string::String);
+
+            async fn from_request_parts(
+                parts: &mut ::axum::http::request::Parts,
+                _state: &S,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                let source = #source;
+                ::std::result::Result::Ok(Self {
+                    #(#values)*
+                })
+            }
+        }
+    })
+}
+
+/// Finds the request body in the arguments of a handler that aren't extracted from the path,
+/// query, headers or cookies. The body is either `declared`, or the last argument if it is a
+/// body extractor such as `Json<T>`.
+///
+/// Axum only allows the last extractor to consume the request body, so a body extractor
+/// anywhere else is an error.
+fn find_body(declared: Option<Type>, args: &[&PatType]) -> syn::Result<Option<RequestBody>> {
+    let not_last = |arg: &PatType| {
+        syn::Error::new_spanned(
+            &arg.ty,
+            format!(
+                "the body extractor `{}` must come after the other extractors of the handler",
+                type_string(&arg.ty)
+            ),
+        )
+    };
+
+    if let Some(declared) = declared {
+        let position = args
+            .iter()
+            .position(|arg| type_string(&arg.ty) == type_string(&declared))
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &declared,
+                    format!(
+                        "body `{}` not found in function arguments",
+                        type_string(&declared)
+                    ),
+                )
+            })?;
+        if position + 1 != args.len() {
+            return Err(not_last(args[position]));
+        }
+        return Ok(Some(RequestBody {
+            ty: Box::new(declared),
+            declared: true,
+        }));
+    }
+
+    if let Some((_last, others)) = args.split_last() {
+        if let Some(arg) = others.iter().find(|arg| is_body_extractor(&arg.ty)) {
+            return Err(not_last(arg));
+        }
+    }
+    Ok(args
+        .last()
+        .filter(|arg| is_body_extractor(&arg.ty))
+        .map(|arg| RequestBody {
+            ty: arg.ty.clone(),
+            declared: false,
+        }))
+}
+
+/// Whether the last segment of `ty` is a well-known extractor that consumes the request body.
+fn is_body_extractor(ty: &Type) -> bool {
+    const BODY_EXTRACTORS: &[&str] = &[
+        "Json",
+        "Form",
+        "String",
+        "Bytes",
+        "Body",
+        "Multipart",
+        "RawForm",
+        "Request",
+    ];
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| BODY_EXTRACTORS.iter().any(|name| segment.ident == name)),
+        _ => false,
+    }
+}
+
+/// The content type of the body extractor `ty`, if it is well-known.
+fn body_content_type(ty: &Type) -> Option<&'static str> {
+    let Type::Path(ty) = ty else {
+        return None;
+    };
+    match ty.path.segments.last()?.ident.to_string().as_str() {
+        "Json" => Some("application/json"),
+        "Form" => Some("application/x-www-form-urlencoded"),
+        "String" => Some("text/plain"),
+        "Bytes" => Some("application/octet-stream"),
+        "Multipart" => Some("multipart/form-data"),
+        _ => None,
+    }
+}
+
+/// The type the schema of the body extractor `ty` is generated from, such as `T` for `Json<T>`.
+fn body_schema_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last_segment = path.path.segments.last()?;
+    match last_segment.ident.to_string().as_str() {
+        "String" => Some(ty),
+        "Json" | "Form" => match &last_segment.arguments {
+            PathArguments::AngleBracketed(args) => match args.args.first()? {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Adds the identifiers in `tokens` to `idents`, including the names of lifetimes.
+fn collect_idents(tokens: TokenStream2, idents: &mut HashSet<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+/// Generates an OpenAPI parameter `name` of type `ty`, located in `location`, which is the
+/// name of a `utoipa::openapi::path::ParameterIn` variant, such as `Query`.
+fn openapi_parameter(name: String, location: &str, required: bool, ty: &Type) -> TokenStream2 {
+    let utoipa = quote! { ::axum_controller::openapi::utoipa };
+    let location = Ident::new(location, Span::call_site());
+    let required = match required {
+        true => quote!(True),
+        false => quote!(False),
+    };
+    quote! {
+        #utoipa::openapi::path::ParameterBuilder::new()
+            .name(#name)
+            .parameter_in(#utoipa::openapi::path::ParameterIn::#location)
+            .required(#utoipa::openapi::Required::#required)
+            .schema(Some(<#ty as #utoipa::PartialSchema>::schema()))
+            .build()
+    }
+}
+
+/// Generates an `axum_controller::ParamInfo` for the parameter `name` of type `ty`.
+fn param_info(name: &str, ty: &Type) -> TokenStream2 {
+    let ty = type_string(ty);
+    quote! { ::axum_controller::ParamInfo { name: #name, ty: #ty } }
+}
+
+/// Formats `ty` like it would be written by hand, e.g. `Option<u32>` instead of the
+/// `Option < u32 >` of its token stream.
+pub fn type_string(ty: &Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
 fn guess_state_type(sig: &syn::Signature) -> Type {
     for arg in &sig.inputs {
         if let FnArg::Typed(pat_type) = arg {
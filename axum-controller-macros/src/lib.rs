@@ -1,16 +1,16 @@
-#![feature(proc_macro_diagnostic)]
-use compilation::CompiledRoute;
+use compilation::{type_string, CompiledRoute};
 use parsing::{Method, Route};
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use proc_macro2::{Group, Ident, Span, TokenStream as TokenStream2, TokenTree};
+use quote::ToTokens;
 use std::collections::HashMap;
 use syn::{
-    meta::parser,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
+    spanned::Spanned,
     token::{Comma, Slash},
-    Attribute, FnArg, GenericArgument, Item, ItemFn, ItemImpl, Lit, LitStr, Meta, MetaNameValue,
-    Path, PathArguments, Signature, Type,
+    Attribute, FnArg, GenericArgument, GenericParam, Generics, ImplItem, ItemFn, ItemImpl, LitStr,
+    Meta, PathArguments, Signature, Type, WherePredicate,
 };
 #[macro_use]
 extern crate quote;
@@ -24,20 +24,91 @@ mod parsing;
 ///
 /// # Syntax
 /// ```ignore
-/// #[route(<METHOD> "<PATH>" [with <STATE>])]
+/// #[route(<METHOD> [| <METHOD>]* "<PATH>" [with <STATE>] [, headers(<HEADER>, ..)] [, cookies(<COOKIE>, ..)] [, body = <BODY>] [, url] [, client])]
 /// ```
-/// - `METHOD` is the HTTP method, such as `GET`, `POST`, `PUT`, etc.
+/// - `METHOD` is the HTTP method, such as `GET`, `POST`, `PUT`, `PATCH`, etc. Methods without
+///   a dedicated axum routing function, such as `PROPFIND`, are supported when written in
+///   uppercase, unless they are a likely typo of a standard method (e.g. `GTE`). A method
+///   written as a string literal, such as `"READ"` or `"M-SEARCH"`, is always taken exactly.
+///   Multiple methods can be served by the same handler by separating them with `|`, e.g.
+///   `GET | HEAD`. Routes with custom methods answer every method that isn't listed with
+///   `405 Method Not Allowed` and an `Allow` header, except `HEAD` when `GET` is listed.
 /// - `PATH` is the path of the route, with optional path parameters and query parameters,
-///     e.g. `/item/:id?amount&offset`.
+///   e.g. `/item/{id}?amount&offset`. Captures are written as `{id}`, wildcards as `{*rest}`.
+///   The legacy syntax `:id` and `*rest` is supported as well, and emits a deprecation warning
+///   when the `legacy-path-warnings` feature is enabled.
 /// - `STATE` is the type of axum-state, passed to the handler. This is optional, and if not
-///    specified, the state type is guessed based on the parameters of the handler.
+///   specified, the state type is guessed based on the parameters of the handler.
+///
+/// Captures can be constrained by writing a constraint in angle brackets after their name, which
+/// is only allowed on `:name`/`{name}` captures. A request whose capture doesn't satisfy the
+/// constraint is answered with `404 Not Found`, as if the route didn't match, instead of the
+/// `400 Bad Request` of a capture that can't be parsed. However, the router doesn't fall through
+/// to another route for the request, so routes that only differ by their constraints, such as
+/// `/{id<u32>}` and `/{slug}`, still conflict. A constraint is either:
+/// - a type, e.g. `{id<u32>}`, which the capture must parse as with `FromStr`.
+/// - an integer range with at least one bound, e.g. `{page<1..=100>}` or `{page<1..>}`.
+/// - a regex that must match the whole capture, e.g. `{slug<[a-z-]+>}` or `:id<\d+>`. Regexes
+///   are checked at compile time, and compiled once at runtime. A regex that is a single
+///   identifier, such as `abc`, is read as a type, and can be wrapped in parentheses instead.
+///
+/// Query parameters are extracted by name, and can be renamed in the query with `as`, or given
+/// a default value for when they are missing with `=`. For example,
+/// `?page_size as "page-size" = 20&sort` extracts `page_size` from the `page-size` key of the
+/// query, defaulting to `20`.
+/// Query parameters of type `Vec<T>`, `HashSet<T>` or `BTreeSet<T>` collect every occurrence of
+/// a repeated key, such as `?tag=a&tag=b`, and are empty when the key is missing. An `Option` of
+/// a collection, such as `Option<Vec<T>>`, is `None` when the key is missing instead.
+///
+/// Header parameters are declared with `headers(..)`, and are parsed from the request headers
+/// with `FromStr`. A header is named after its parameter, with underscores replaced by dashes,
+/// unless it's named explicitly with `=`. For example,
+/// `headers(if_match, request_id = "X-Request-Id")` extracts `if_match` from the `if-match`
+/// header, and `request_id` from the `X-Request-Id` header.
+/// Headers of type `Option<T>` are optional. Missing or invalid headers are answered with
+/// `400 Bad Request`.
+///
+/// With the `cookies` feature, cookie parameters are declared with `cookies(..)` in the same way,
+/// e.g. `cookies(session_id, locale = "lang")`. Cookies are named after their parameter, and are
+/// read from axum-extra's `CookieJar`.
+///
+/// Path and query parameters are bound to the argument of the handler with the same name. An
+/// argument with a destructuring pattern, such as a newtype wrapper, is bound with the `#[path]`
+/// or `#[query]` attribute instead. The parameter is the single identifier in the pattern, or
+/// can be named explicitly:
+/// ```ignore
+/// #[route(GET "/users/{id}/posts?page")]
+/// async fn user_posts(#[path] UserId(id): UserId, #[query("page")] Page(p): Page) -> String {
+///     todo!("handle request")
+/// }
+/// ```
+///
+/// The attributes also declare where an argument comes from without the route string:
+/// `#[query]` adds a query parameter, `#[header]` or `#[header("X-Name")]` adds a header and
+/// `#[body]` marks the request body. Path parameters must still be captured by the path, and
+/// every capture must be bound to exactly one argument. The attributes are removed from the
+/// generated handler.
+/// ```ignore
+/// #[route(POST "/items/{id}")]
+/// async fn update_item(
+///     #[path] id: u32,
+///     #[query] dry_run: Option<bool>,
+///     #[header("X-Request-Id")] request_id: Option<String>,
+///     #[body] Json(item): Json<Item>,
+/// ) -> String {
+///     todo!("handle request")
+/// }
+/// ```
+///
+/// The `responses`, `security` and `tags` options of `api_route` are accepted as well. They are
+/// only added to the generated documentation of the handler.
 ///
 /// # Example
 /// ```
 /// use axum::extract::{State, Json};
 /// use axum_controller_macros::route;
 ///
-/// #[route(GET "/item/:id?amount&offset")]
+/// #[route(GET "/item/{id}?amount&offset")]
 /// async fn item_handler(
 ///     id: u32,
 ///     amount: Option<u32>,
@@ -55,20 +126,79 @@ mod parsing;
 /// This should work for most cases, however when not sufficient, the state type can be specified
 /// explicitly using the `with` keyword:
 /// ```ignore
-/// #[route(GET "/item/:id?amount&offset" with String)]
+/// #[route(GET "/item/{id}?amount&offset" with String)]
+/// ```
+///
+/// # Request body
+/// The request body of the route is the last argument of the handler, if it is a well-known
+/// body extractor such as `Json<T>`, `Form<T>`, `String` or `Bytes`. It can also be declared
+/// explicitly with `body = <BODY>`, where `BODY` is written exactly like the type of the
+/// argument, e.g. `body = Json<NewItem>`. The body is described in the route info, and declared
+/// `Json<T>` and `Form<T>` bodies are documented in OpenAPI with the schema of `T`.
+///
+/// Axum requires the body extractor to come after the other extractors of the handler, which
+/// is checked by the macro:
+/// ```compile_fail
+/// use axum::extract::{Json, State};
+/// use axum_controller_macros::route;
+///
+/// #[route(POST "/item")]
+/// async fn item_handler(Json(json): Json<u32>, State(state): State<String>) -> String {
+///     todo!("handle request")
+/// }
+/// ```
+///
+/// # Route info
+/// The macro also generates a module with the same name as the handler, containing a function
+/// `fn info() -> &'static axum_controller::RouteInfo`, which describes the methods, path,
+/// parameters, documentation and state type of the route.
+///
+/// # Url generation
+/// With the `url` option, the module of the handler also contains a function `url` which builds
+/// the url of the route. Its arguments are the path-parameters followed by the query-parameters,
+/// and optional query-parameters are left out of the url when they are `None`:
+/// ```ignore
+/// #[route(GET "/item/{id}?amount&offset", url)]
+/// async fn item_handler(id: u32, amount: Option<u32>, offset: Option<u32>) -> String {
+///     todo!("handle request")
+/// }
+///
+/// assert_eq!(item_handler::url(1, Some(2), None), "/item/1?amount=2");
 /// ```
+/// The parameters are written to the url with `Display`, so their types must implement it. The
+/// function has the generic parameters of the handler that these types use.
+///
+/// With the `client` option, which requires the `client` feature, the module also contains a
+/// function `request`, which takes an `axum_controller::client::Client` followed by the
+/// arguments of `url`, the headers and the cookies of the route, and starts a request to the
+/// route. If the body of the route is `Json<T>` or `Form<T>`, it takes a `body: &T` as well (so
+/// `T` must implement `Serialize`), and if it's a `String`, a `body: String`. Routes with
+/// multiple methods get a function per method instead, such as `request_put` and
+/// `request_patch` for `PUT | PATCH`.
+///
+/// # Registry
+/// With the `registry` feature, the route is registered in the global registry of
+/// `axum-controller`, and added to a router by `TypedRouter::auto_routes`. Routes with generic
+/// parameters are not registered.
 ///
 /// # Internals
-/// The macro expands to a function with signature `fn() -> (&'static str, axum::routing::MethodRouter<S>)`.
-/// The first element of the tuple is the path, and the second is axum's `MethodRouter`.
+/// The macro expands to a function with signature
+/// `fn() -> (&'static axum_controller::RouteInfo, axum::routing::MethodRouter<S>)`.
+/// The first element of the tuple describes the route, and the second is axum's `MethodRouter`.
 ///
 /// The path and query are extracted using axum's `extract::Path` and `extract::Query` extractors, as the first
-/// and second parameters of the function. The remaining parameters are the parameters of the handler.
+/// and second parameters of the function, followed by the headers. The remaining parameters are
+/// the parameters of the handler.
+///
+/// In debug builds, the inner function is annotated with axum's `debug_handler`, so that an
+/// argument which isn't a valid extractor is reported at the argument of the handler. Handlers
+/// with generic parameters are not checked.
 #[proc_macro_attribute]
-pub fn route(attr: TokenStream, mut item: TokenStream) -> TokenStream {
-    match _route(attr, item.clone()) {
+pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match _route(attr, item.clone(), false) {
         Ok(tokens) => tokens.into(),
         Err(err) => {
+            let mut item = without_binding_attrs(item);
             let err: TokenStream = err.to_compile_error().into();
             item.extend(err);
             item
@@ -76,152 +206,896 @@ pub fn route(attr: TokenStream, mut item: TokenStream) -> TokenStream {
     }
 }
 
-fn _route(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream2> {
+/// Same as [`macro@route`], but also documents the route with OpenAPI, using `utoipa`.
+///
+/// # Syntax
+/// ```ignore
+/// #[api_route(<METHOD> "<PATH>" [with <STATE>] [, responses = { <STATUS>: <TYPE>, .. }]
+///     [, security = { "<SCHEME>": ["<SCOPE>", ..], .. }] [, tags = ["<TAG>", ..]])]
+/// ```
+/// - `responses` are the possible responses of the handler, with the type of their JSON body.
+///   The types must implement `utoipa::ToSchema`.
+/// - `security` are the security requirements of the route, with their required scopes.
+/// - `tags` are the OpenAPI tags of the route.
+///
+/// The types of the path- and query-parameters must implement `utoipa::PartialSchema`. The
+/// first line of the doc comments of the handler is used as summary, and the remaining lines as
+/// description. The `CONNECT` method and custom methods are not documented, and a route with
+/// only these methods is an error.
+///
+/// # Example
+/// ```
+/// use axum_controller::api_route;
+///
+/// /// Get an item.
+/// #[api_route(GET "/item/{id}?amount", responses = { 200: String }, tags = ["items"])]
+/// async fn item_handler(id: u32, amount: Option<u32>) -> String {
+///     todo!("handle request")
+/// }
+/// ```
+///
+/// # Internals
+/// The macro expands to a function with signature
+/// `fn() -> utoipa_axum::router::UtoipaMethodRouter<S>`, which can be added to a
+/// `utoipa_axum::router::OpenApiRouter` with `TypedApiRouter::typed_api_route`.
+#[cfg(feature = "openapi")]
+#[proc_macro_attribute]
+pub fn api_route(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match _route(attr, item.clone(), true) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => {
+            let mut item = without_binding_attrs(item);
+            let err: TokenStream = err.to_compile_error().into();
+            item.extend(err);
+            item
+        }
+    }
+}
+
+fn _route(attr: TokenStream, item: TokenStream, with_openapi: bool) -> syn::Result<TokenStream2> {
     // Parse the route and function
     let route = syn::parse::<Route>(attr)?;
-    let function = syn::parse::<ItemFn>(item)?;
+    let mut function = syn::parse::<ItemFn>(item)?;
 
     // Now we can compile the route
     let route = CompiledRoute::from_route(route, &function)?;
-    let path_extractor = route.path_extractor();
-    let query_extractor = route.query_extractor();
-    let query_params_struct = route.query_params_struct();
+    parsing::strip_binding_attrs(&mut function.sig);
     let state_type = &route.state;
     let axum_path = route.to_axum_path_string();
-    let http_method = route.method.to_axum_method_name();
-    let remaining_numbered_pats = route.remaining_pattypes_numbered(&function.sig.inputs);
-    let extracted_idents = route.extracted_idents();
-    let remaining_numbered_idents = remaining_numbered_pats.iter().map(|pat_type| &pat_type.pat);
     let route_docs = route.to_doc_comments();
 
     // Get the variables we need for code generation
     let fn_name = &function.sig.ident;
-    let fn_output = &function.sig.output;
     let vis = &function.vis;
-    let asyncness = &function.sig.asyncness;
-    let (impl_generics, ty_generics, where_clause) = &function.sig.generics.split_for_impl();
-    let ty_generics = ty_generics.as_turbofish();
+    let (impl_generics, _ty_generics, where_clause) = &function.sig.generics.split_for_impl();
     let fn_docs = function
         .attrs
         .iter()
         .filter(|attr| attr.path().is_ident("doc"));
 
-    let (inner_fn_call, method_router_ty) = {
+    let turbofish = function.sig.generics.split_for_impl().1.as_turbofish();
+    let inner_fn = inner_function(
+        &route,
+        &function,
+        quote!(#function),
+        quote!(#fn_name #turbofish),
+    );
+    let inner_fn_call = method_router(&route, &function);
+    let warnings = &route.warnings;
+
+    let (return_ty, return_value) = if with_openapi {
+        // `OpenApiRouter` only adds the handler at the paths of its documentation
+        if !route.is_documentable() {
+            return Err(syn::Error::new(
+                route.methods[0].span(),
+                "`CONNECT` and custom methods can't be documented in OpenAPI, use `#[route]` instead",
+            ));
+        }
+        let openapi = route.to_openapi(&axum_path);
         (
-            quote! { ::axum::routing::#http_method(__inner__function__ #ty_generics) },
-            quote! { ::axum::routing::MethodRouter },
+            quote! { ::axum_controller::openapi::UtoipaMethodRouter<#state_type> },
+            quote! {
+                let (schemas, paths) = #openapi;
+                (schemas, paths, #inner_fn_call)
+            },
+        )
+    } else {
+        (
+            quote! { (&'static ::axum_controller::RouteInfo, ::axum::routing::MethodRouter<#state_type>) },
+            quote! { (#fn_name::info(), #inner_fn_call) },
         )
     };
 
+    // The info is defined in the companion module, but describes the module of the handler
+    let route_info = route.to_route_info(
+        &axum_path,
+        &fn_name.to_string(),
+        quote! { ::axum_controller::__private::parent_module(::core::module_path!()) },
+    );
+    let generics = &function.sig.generics;
+    let url_fn = route
+        .url
+        .then(|| route.to_url_fn(&format_ident!("url"), &parse_quote!(pub), "", generics));
+    let request_fn = route
+        .client
+        .then(|| route.to_request_fns(&format_ident!("request"), &parse_quote!(pub), "", generics));
+    let companion_docs = format!("Companion items of the [`{fn_name}`] route.");
+
+    // Generic routes can't be registered, since the registry only holds concrete routers
+    let mut registrations = Vec::new();
+    if cfg!(feature = "registry") && function.sig.generics.params.is_empty() {
+        // Typed routers record the route info, so the route is added with `typed_route`
+        let (router_body, openapi_router_body) = if with_openapi {
+            (
+                quote! {
+                    ::axum_controller::TypedRouter::typed_route(router, || {
+                        let (_schemas, _paths, method_router) = #fn_name();
+                        (#fn_name::info(), method_router)
+                    })
+                },
+                quote! { router.routes(#fn_name()) },
+            )
+        } else {
+            (
+                quote! { ::axum_controller::TypedRouter::typed_route(router, #fn_name) },
+                quote! {
+                    let (info, method_router) = #fn_name();
+                    router.route(info.axum_path, method_router)
+                },
+            )
+        };
+        registrations.extend([
+            registration(quote! { ::axum::Router<#state_type> }, router_body.clone()),
+            registration(
+                quote! { ::axum_controller::TrackedRouter<#state_type> },
+                router_body,
+            ),
+        ]);
+        if cfg!(feature = "openapi") {
+            registrations.push(registration(
+                quote! { ::axum_controller::openapi::OpenApiRouter<#state_type> },
+                openapi_router_body,
+            ));
+        }
+    }
+
     // Generate the code
     Ok(quote! {
         #(#fn_docs)*
         #route_docs
-        #vis fn #fn_name #impl_generics() -> (&'static str, #method_router_ty<#state_type>) #where_clause {
+        #vis fn #fn_name #impl_generics() -> #return_ty #where_clause {
+            #(#warnings)*
+            #inner_fn
 
-            #query_params_struct
+            #return_value
+        }
 
-            #asyncness fn __inner__function__ #impl_generics(
-                #path_extractor
-                #query_extractor
-                #remaining_numbered_pats
-            ) #fn_output #where_clause {
-                #function
+        #[doc = #companion_docs]
+        #vis mod #fn_name {
+            #[allow(unused_imports)]
+            use super::*;
 
-                #fn_name #ty_generics(#(#extracted_idents,)* #(#remaining_numbered_idents,)* ).await
+            /// Describes the route.
+            pub fn info() -> &'static ::axum_controller::RouteInfo {
+                const INFO: ::axum_controller::RouteInfo = #route_info;
+                &INFO
             }
 
-            (#axum_path, #inner_fn_call)
+            #url_fn
+
+            #request_fn
         }
+
+        #(#registrations)*
     })
 }
 
-#[derive(Debug, Clone, Default)]
+/// Removes the binding attributes from the arguments of the handler in `item`, which is emitted
+/// as is when the route is invalid.
+fn without_binding_attrs(item: TokenStream) -> TokenStream {
+    match syn::parse::<ItemFn>(item.clone()) {
+        Ok(mut function) => {
+            parsing::strip_binding_attrs(&mut function.sig);
+            function.into_token_stream().into()
+        }
+        Err(_) => item,
+    }
+}
+
+/// Generates a registration in the route registry of `axum-controller`, which adds a route to
+/// routers of type `router_ty`, by evaluating `body` with the router bound to `router`.
+fn registration(router_ty: TokenStream2, body: TokenStream2) -> TokenStream2 {
+    quote! {
+        const _: () = {
+            fn __register__(router: #router_ty) -> #router_ty {
+                #body
+            }
+
+            ::axum_controller::__private::inventory::submit! {
+                ::axum_controller::registry::RegisteredRoute::new::<#router_ty>(
+                    &(__register__ as fn(#router_ty) -> #router_ty),
+                )
+            }
+        };
+    }
+}
+
+/// Generates `__inner__function__`, which extracts the path- and query-parameters of the
+/// route and forwards them, together with the remaining arguments, to `callee`.
+///
+/// `prelude` is emitted at the start of the function body, before the call. The generics of
+/// `function` are the generics of `__inner__function__`, and `callee` must include the generic
+/// arguments of the call.
+fn inner_function(
+    route: &CompiledRoute,
+    function: &ItemFn,
+    prelude: TokenStream2,
+    callee: TokenStream2,
+) -> TokenStream2 {
+    let constraints_extractor = route.constraints_extractor();
+    let constraints_struct = route.constraints_struct();
+    let path_extractor = route.path_extractor();
+    let query_extractor = route.query_extractor();
+    let query_params_struct = route.query_params_struct();
+    let header_extractor = route.header_extractor();
+    let header_params_struct = route.header_params_struct();
+    let cookie_extractor = route.cookie_extractor();
+    let cookie_params_struct = route.cookie_params_struct();
+    let remaining_numbered_pats = route.remaining_pattypes_numbered(&function.sig.inputs);
+    let call_args = route.call_args(&function.sig.inputs);
+
+    let fn_output = &function.sig.output;
+    let asyncness = &function.sig.asyncness;
+    let (impl_generics, _ty_generics, where_clause) = &function.sig.generics.split_for_impl();
+
+    // `debug_handler` reports mistakes in the extractors of the handler at the arguments of the
+    // handler, but doesn't support generic functions.
+    let state = &route.state;
+    let debug_handler = function.sig.generics.params.is_empty().then(|| {
+        quote! {
+            #[cfg_attr(
+                debug_assertions,
+                ::axum_controller::__private::axum_macros::debug_handler(state = #state)
+            )]
+        }
+    });
+
+    quote! {
+        #constraints_struct
+        #query_params_struct
+        #header_params_struct
+        #cookie_params_struct
+
+        #debug_handler
+        #asyncness fn __inner__function__ #impl_generics(
+            #constraints_extractor
+            #path_extractor
+            #query_extractor
+            #header_extractor
+            #cookie_extractor
+            #remaining_numbered_pats
+        ) #fn_output #where_clause {
+            #prelude
+
+            #callee(#(#call_args,)*).await
+        }
+    }
+}
+
+/// Generates the `MethodRouter` for `__inner__function__`.
+fn method_router(route: &CompiledRoute, function: &ItemFn) -> TokenStream2 {
+    let ty_generics = function.sig.generics.split_for_impl().1;
+    let ty_generics = ty_generics.as_turbofish();
+    route.to_method_router(quote!(__inner__function__ #ty_generics))
+}
+
+#[derive(Clone, Default)]
 struct MyAttrs {
-    middlewares: Vec<syn::Expr>,
-    path: Option<syn::Expr>,
-    state: Option<syn::Expr>,
+    /// The `middleware` and `route_middleware` layers, in the order they are declared.
+    layers: Vec<(LayerKind, syn::Expr)>,
+    path: Option<LitStr>,
+    state: Option<Type>,
+}
+
+#[derive(Clone, Copy)]
+enum LayerKind {
+    /// Applied with `Router::layer`.
+    Layer,
+    /// Applied with `Router::route_layer`.
+    RouteLayer,
 }
 
 impl Parse for MyAttrs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut path: Option<syn::Expr> = None;
-        let mut state: Option<syn::Expr> = None;
-        let mut middlewares: Vec<syn::Expr> = Vec::new();
-
-        // parse while stuff returns
-        for nv in Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)?.into_iter() {
-            // syn::MetaNameValue::parse(input) {
-            let segs = nv.path.segments.clone().into_pairs();
-            let seg = segs.into_iter().next().unwrap().into_value();
-            let ident = seg.ident;
+        let mut path: Option<LitStr> = None;
+        let mut state: Option<Type> = None;
+        let mut layers = Vec::new();
+
+        let mut errors = Vec::new();
+
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            let _ = input.parse::<Token![=]>()?;
             match ident.to_string().as_str() {
                 "path" => {
-                    if path.is_some() {
-                        return Err(syn::Error::new_spanned(path, "duplicate `path` attribute"));
+                    let lit = input.parse::<LitStr>()?;
+                    if !lit.value().starts_with('/') {
+                        errors.push(syn::Error::new(
+                            lit.span(),
+                            "expected path to start with '/'",
+                        ));
+                    }
+                    if path.replace(lit).is_some() {
+                        errors.push(syn::Error::new(ident.span(), "duplicate `path` attribute"));
                     }
-                    path = Some(nv.value);
-                    // panic!("{test:?}");
-                    // panic!("{nv:?}");
                 }
                 "state" => {
-                    if state.is_some() {
-                        return Err(syn::Error::new_spanned(
-                            state,
-                            "duplicate `state` attribute",
-                        ));
+                    if state.replace(input.parse::<Type>()?).is_some() {
+                        errors.push(syn::Error::new(ident.span(), "duplicate `state` attribute"));
                     }
-                    state = Some(nv.value);
                 }
-                "middleware" => middlewares.push(nv.value),
-                _ => {
-                    panic!("123");
+                "middleware" => layers.push((LayerKind::Layer, input.parse::<syn::Expr>()?)),
+                "route_middleware" => {
+                    layers.push((LayerKind::RouteLayer, input.parse::<syn::Expr>()?))
                 }
+                key => {
+                    const OPTIONS: &[&str] = &["path", "state", "middleware", "route_middleware"];
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        parsing::with_suggestion(
+                            format!("expected one of ({})", OPTIONS.join(", ")),
+                            key,
+                            OPTIONS.iter().copied(),
+                        ),
+                    ));
+                    // Skip the value, to report the errors in the other options as well
+                    input.parse::<syn::Expr>()?;
+                }
+            }
+            if input.is_empty() {
+                break;
             }
+            let _ = input.parse::<Token![,]>()?;
         }
+        parsing::combine_errors(errors)?;
+
         Ok(Self {
             state,
             path,
-            middlewares,
+            layers,
         })
     }
 }
 
-#[derive(Debug, Clone, Default)]
-struct MyItem<F, _>
-where
-    F: Fn(_) -> String,
-{
-    typed_routing_fn: F,
+/// A macro that generates an axum router from all [`route`]-annotated methods of an impl block.
+///
+/// # Syntax
+/// ```ignore
+/// #[controller([path = "<PATH>"] [, state = <STATE>] [, middleware = <LAYER>]* [, route_middleware = <LAYER>]*)]
+/// ```
+/// - `PATH` is the base path that the paths of all routes are prefixed with. This is optional,
+///   and defaults to `/`.
+/// - `STATE` is the type of axum-state shared by all routes of the controller. This is optional,
+///   and defaults to `()`. Routes that don't specify a state type with `with` use this state.
+/// - `LAYER` is a tower layer, such as `axum::middleware::from_fn(auth)`. Every `middleware` is
+///   applied with `Router::layer` and every `route_middleware` with `Router::route_layer`, in the
+///   order they are declared. As with axum, a layer wraps all layers declared before it, and
+///   `route_middleware` requires the controller to have at least one route.
+///
+/// # Example
+/// ```
+/// use axum_controller_macros::controller;
+///
+/// struct UsersController;
+///
+/// #[controller(path = "/users", state = String)]
+/// impl UsersController {
+///     #[route(GET "/{id}")]
+///     async fn get(id: u32) -> String {
+///         format!("user {id}")
+///     }
+/// }
+///
+/// let router: axum::Router<String> = UsersController::into_router();
+/// ```
+///
+/// # Receivers
+/// Routes can take `self` in any form, such as `&self` or `self: Arc<Self>`. The receiver is
+/// extracted from the state using axum's `State` extractor, so the receiver type must implement
+/// `FromRef<STATE>`. This allows controllers to hold their own dependencies:
+/// ```
+/// use axum::extract::FromRef;
+/// use axum_controller_macros::controller;
+///
+/// #[derive(Clone)]
+/// struct AppState {
+///     greeting: String,
+/// }
+///
+/// #[derive(Clone)]
+/// struct GreetController {
+///     greeting: String,
+/// }
+///
+/// impl FromRef<AppState> for GreetController {
+///     fn from_ref(state: &AppState) -> Self {
+///         Self { greeting: state.greeting.clone() }
+///     }
+/// }
+///
+/// #[controller(path = "/greet", state = AppState)]
+/// impl GreetController {
+///     #[route(GET "/{name}")]
+///     async fn greet(&self, name: String) -> String {
+///         format!("{}, {name}!", self.greeting)
+///     }
+/// }
+/// ```
+///
+/// # Collisions
+/// Routes that axum would reject when creating the router are reported at compile-time:
+/// two routes handling the same method at the same path, captures with different names at the
+/// same position (such as `/{id}` and `/{name}`), and routes with a custom method sharing
+/// their path with another route.
+/// ```compile_fail
+/// # use axum_controller_macros::controller;
+/// struct UsersController;
+///
+/// #[controller(path = "/users")]
+/// impl UsersController {
+///     #[route(GET "/{id}")]
+///     async fn get(id: u32) {}
+///
+///     #[route(GET | DELETE "/{id}")]
+///     async fn get_or_delete(id: u32) {}
+/// }
+/// ```
+///
+/// # Route info
+/// For every route, the macro adds an associated function
+/// `fn <name>_info() -> &'static axum_controller::RouteInfo`, like the `info` function generated
+/// by [`macro@route`].
+///
+/// # Url generation
+/// For every route with the `url` option, the macro adds an associated function `<name>_url`,
+/// which builds the url of the route like the `url` function generated by [`macro@route`]:
+/// ```ignore
+/// assert_eq!(UsersController::get_url(1), "/users/1");
+/// ```
+///
+/// For every route with the `client` option, a `<name>_request` function is added, like the
+/// `request` function generated by [`macro@route`].
+///
+/// # Registry
+/// With the `registry` feature, the routes of the controller are registered in the global
+/// registry of `axum-controller` as a whole, like [`macro@route`] does. Controllers with
+/// generic parameters are not registered.
+///
+/// # OpenAPI
+/// With the `openapi` feature, the macro also adds an associated function
+/// `fn into_openapi_router() -> utoipa_axum::router::OpenApiRouter<STATE>`, which contains every
+/// route of the controller. Routes annotated with `#[api_route]`, or with the `responses`,
+/// `security` or `tags` option, are documented as [`macro@api_route`] does. Other routes are
+/// added without documentation, so their types don't have to implement
+/// `utoipa::PartialSchema`, as are routes with only `CONNECT` or custom methods.
+///
+/// # Internals
+/// The `#[route]` attributes are removed from the methods, which stay callable as normal
+/// associated functions. The macro adds an associated function
+/// `fn into_router() -> axum::Router<STATE>`, which registers every route under `PATH` and then
+/// applies the middleware. Layers only wrap the routes of this controller, not the routes of
+/// routers it is merged into.
+///
+/// The controller also implements `axum_controller::Controller`, so that it can be added to a
+/// router with `TypedRouter::typed_controller`, which records its routes in a `TrackedRouter`.
+///
+/// The impl block may be generic, e.g. `impl<T: Send + Sync + 'static> Controller<T>`, in which
+/// case the handlers can use its generic parameters. The routes themselves can't have generic
+/// parameters, since `into_router` can't choose them.
+#[proc_macro_attribute]
+pub fn controller(attr: TokenStream, mut item: TokenStream) -> TokenStream {
+    match _controller(attr, item.clone()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => {
+            // The route attributes can't be expanded inside the impl block, so they are removed
+            // to avoid follow-up errors
+            if let Ok(mut item_impl) = syn::parse::<ItemImpl>(item.clone()) {
+                for impl_item in &mut item_impl.items {
+                    if let ImplItem::Fn(method) = impl_item {
+                        method.attrs.retain(|attr| !is_route_attr(attr));
+                        parsing::strip_binding_attrs(&mut method.sig);
+                    }
+                }
+                item = item_impl.into_token_stream().into();
+            }
+            let err: TokenStream = err.to_compile_error().into();
+            item.extend(err);
+            item
+        }
+    }
+}
+
+fn _controller(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream2> {
+    let mut item_impl = syn::parse::<ItemImpl>(item)?;
+    // Errors are collected, so that the errors of every route are reported at once
+    let mut errors = Vec::new();
+    let attrs = syn::parse::<MyAttrs>(attr)
+        .map_err(|err| errors.push(err))
+        .unwrap_or_default();
+
+    let self_ty = &item_impl.self_ty;
+    let state_type = attrs.state.unwrap_or_else(|| parse_quote!(()));
+    let base_path = attrs.path.map(|lit| lit.value()).unwrap_or_default();
+
+    let mut routes = Vec::new();
+    let mut api_routes = Vec::new();
+    let mut companion_fns = Vec::new();
+    let mut info_fns = Vec::new();
+    let mut signatures = Vec::<RouteSignature>::new();
+    for impl_item in &mut item_impl.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        let Some(index) = method.attrs.iter().position(is_route_attr) else {
+            continue;
+        };
+        let route_attr = method.attrs.remove(index);
+        let is_api_route = is_api_route_attr(&route_attr);
+        let mut route = match route_attr.parse_args::<Route>() {
+            Ok(route) => route,
+            Err(err) => {
+                errors.push(err);
+                continue;
+            }
+        };
+        if route.state.is_none() {
+            route.state = Some(state_type.clone());
+        }
+
+        // The generated inner function is not part of the impl block, so it can't refer to `Self`
+        let mut sig: Signature = syn::parse2(replace_self(method.sig.to_token_stream(), self_ty))?;
+        if let Some(FnArg::Receiver(receiver)) = sig.inputs.first_mut() {
+            // The type of a shorthand receiver, such as `&self`, is not part of the tokens
+            receiver.ty = syn::parse2(replace_self(receiver.ty.to_token_stream(), self_ty))?;
+        }
+        // The inner function is defined inside of the impl block's functions, so it can't use the
+        // generics of the impl block, and declares them itself instead
+        let method_turbofish = sig
+            .generics
+            .split_for_impl()
+            .1
+            .as_turbofish()
+            .to_token_stream();
+        sig.generics = merge_generics(&item_impl.generics, &sig.generics);
+        let mut function = ItemFn {
+            attrs: method.attrs.clone(),
+            vis: method.vis.clone(),
+            sig,
+            block: Box::new(method.block.clone()),
+        };
+        let route = match CompiledRoute::from_route(route, &function) {
+            Ok(route) => route,
+            Err(err) => {
+                errors.push(err);
+                continue;
+            }
+        };
+        parsing::strip_binding_attrs(&mut function.sig);
+        parsing::strip_binding_attrs(&mut method.sig);
+        let route_docs = route.to_doc_comments();
+        method.attrs.push(parse_quote!(#route_docs));
+
+        let fn_name = &function.sig.ident;
+        if route.url {
+            companion_fns.push(route.to_url_fn(
+                &format_ident!("{}_url", fn_name),
+                &method.vis,
+                &base_path,
+                &method.sig.generics,
+            ));
+        }
+        if route.client {
+            companion_fns.push(route.to_request_fns(
+                &format_ident!("{}_request", fn_name),
+                &method.vis,
+                &base_path,
+                &method.sig.generics,
+            ));
+        }
+        let axum_path = join_paths(&base_path, &route.to_axum_path_string());
+        let route_info = route.to_route_info(
+            &axum_path,
+            &format!("{}::{fn_name}", type_string(self_ty)),
+            quote! { ::core::module_path!() },
+        );
+        let info_fn = format_ident!("{}_info", fn_name);
+        info_fns.push(info_fn.clone());
+        let method_vis = &method.vis;
+        let info_fn_doc = format!("Describes the `{fn_name}` route.");
+        companion_fns.push(quote! {
+            #[doc = #info_fn_doc]
+            #method_vis fn #info_fn() -> &'static ::axum_controller::RouteInfo {
+                const INFO: ::axum_controller::RouteInfo = #route_info;
+                &INFO
+            }
+        });
+        signatures.push(RouteSignature {
+            path: axum_path.clone(),
+            methods: route.methods.iter().map(Method::as_http_str).collect(),
+            any_method: route
+                .methods
+                .iter()
+                .any(|method| matches!(method, Method::Custom(_))),
+            attr: route_attr,
+        });
+        let inner_fn = inner_function(
+            &route,
+            &function,
+            quote!(),
+            quote!(<#self_ty>::#fn_name #method_turbofish),
+        );
+        let inner_fn_call = method_router(&route, &function);
+        // Only emitted once, in `into_router`
+        let warnings = &route.warnings;
+        routes.push(quote! {
+            let router = {
+                #(#warnings)*
+                #inner_fn
+
+                router.route(#axum_path, #inner_fn_call)
+            };
+        });
+        // Documenting a route requires its types to implement `PartialSchema`, so only the
+        // routes that ask for documentation are documented
+        let documented = (is_api_route
+            || route.responses.is_some()
+            || route.security.is_some()
+            || route.tags.is_some())
+            && route.is_documentable();
+        if cfg!(feature = "openapi") && documented {
+            let openapi = route.to_openapi(&axum_path);
+            api_routes.push(quote! {
+                let router = {
+                    #inner_fn
+
+                    let (schemas, paths) = #openapi;
+                    router.routes((schemas, paths, #inner_fn_call))
+                };
+            });
+        } else if cfg!(feature = "openapi") {
+            api_routes.push(quote! {
+                let router = {
+                    #inner_fn
+
+                    router.route(#axum_path, #inner_fn_call)
+                };
+            });
+        }
+    }
+
+    errors.extend(check_collisions(&signatures).err());
+    // axum panics when a route layer is added to a router without routes
+    if signatures.is_empty() {
+        for (kind, layer) in &attrs.layers {
+            if let LayerKind::RouteLayer = kind {
+                errors.push(syn::Error::new_spanned(
+                    layer,
+                    "`route_middleware` requires the controller to have at least one route",
+                ));
+            }
+        }
+    }
+    parsing::combine_errors(errors)?;
+
+    // Spanned to the expression, so that invalid layers are reported at their declaration
+    let layers = attrs
+        .layers
+        .iter()
+        .map(|(kind, layer)| match kind {
+            LayerKind::Layer => quote_spanned!(layer.span()=> let router = router.layer(#layer);),
+            LayerKind::RouteLayer => {
+                quote_spanned!(layer.span()=> let router = router.route_layer(#layer);)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let into_openapi_router = cfg!(feature = "openapi").then(|| {
+        quote! {
+            /// Creates an `OpenApiRouter` containing all routes of this controller, together
+            /// with their OpenAPI documentation.
+            pub fn into_openapi_router() -> ::axum_controller::openapi::OpenApiRouter<#state_type> {
+                let router = ::axum_controller::openapi::OpenApiRouter::default();
+                #(#api_routes)*
+                #(#layers)*
+                router
+            }
+        }
+    });
+
+    let mut registrations = Vec::new();
+    if cfg!(feature = "registry") && item_impl.generics.params.is_empty() {
+        let router_body =
+            quote! { ::axum_controller::TypedRouter::typed_controller::<#self_ty>(router) };
+        registrations.extend([
+            registration(quote! { ::axum::Router<#state_type> }, router_body.clone()),
+            registration(
+                quote! { ::axum_controller::TrackedRouter<#state_type> },
+                router_body,
+            ),
+        ]);
+        if cfg!(feature = "openapi") {
+            registrations.push(registration(
+                quote! { ::axum_controller::openapi::OpenApiRouter<#state_type> },
+                quote! { router.merge(<#self_ty>::into_openapi_router()) },
+            ));
+        }
+    }
+
+    let (impl_generics, _ty_generics, where_clause) = item_impl.generics.split_for_impl();
+    Ok(quote! {
+        #item_impl
+
+        #(#registrations)*
+
+        impl #impl_generics #self_ty #where_clause {
+            /// Creates an axum router containing all routes of this controller.
+            pub fn into_router() -> ::axum::Router<#state_type> {
+                let router = ::axum::Router::new();
+                #(#routes)*
+                #(#layers)*
+                router
+            }
+
+            #into_openapi_router
+
+            #(#companion_fns)*
+        }
+
+        impl #impl_generics ::axum_controller::Controller for #self_ty #where_clause {
+            type State = #state_type;
+
+            fn into_router() -> ::axum::Router<#state_type> {
+                <#self_ty>::into_router()
+            }
+
+            fn routes() -> ::std::vec::Vec<&'static ::axum_controller::RouteInfo> {
+                ::std::vec![#(<#self_ty>::#info_fns()),*]
+            }
+        }
+    })
 }
 
-impl<F: Fn(A), _> Parse for MyItem {
-    fn parse(input: ParseStream) -> syn::Result<Self> {}
+/// The method and path of a controller route, used to detect routes that axum would reject.
+struct RouteSignature {
+    path: String,
+    methods: Vec<String>,
+    /// Whether the route is served for any method, which is the case for custom methods.
+    any_method: bool,
+    attr: Attribute,
 }
 
-#[proc_macro_attribute]
-pub fn controller(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let args = parse_macro_input!(attr as MyAttrs);
-    let item_impl = parse_macro_input!(item as MyItem);
-    // Punctuated<>
+impl RouteSignature {
+    /// The path with the names of its captures removed.
+    fn normalized_path(&self) -> String {
+        self.path
+            .split('/')
+            .map(|segment| match segment {
+                _ if segment.starts_with("{*") => "{*}",
+                _ if segment.starts_with('{') => "{}",
+                _ => segment,
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Describes why `self` can't be added to a router that already contains `other`.
+    fn collision(&self, other: &Self) -> Option<String> {
+        if self.normalized_path() != other.normalized_path() {
+            return None;
+        }
+        if self.path != other.path {
+            return Some(format!(
+                "route `{}` conflicts with route `{}`, captures at the same position must have the same name",
+                self.path, other.path
+            ));
+        }
 
-    panic!("{item_impl:?}");
-    for item in item_impl.items.into_iter() {
-        proc_macro::Diagnostic::new(proc_macro::Level::Warning, "test").emit();
-        if let syn::Item::Macro(inner) = item {
-            panic!("{inner:?}")
+        if self.any_method || other.any_method {
+            return Some(format!(
+                "route `{}` conflicts with another route at the same path, routes with custom methods can't share their path",
+                self.path
+            ));
         }
-        panic!("aaa{item:?}");
+        self.methods
+            .iter()
+            .find(|method| other.methods.contains(method))
+            .map(|method| format!("duplicate route `{method} {}`", self.path))
     }
+}
 
-    panic!("bbb {args:?}");
-    // let input = parse_macro_input!(input as ItemImpl);
+/// Checks that no two routes of a controller collide, which would make axum panic when the
+/// router is created.
+fn check_collisions(signatures: &[RouteSignature]) -> syn::Result<()> {
+    let mut errors = Vec::new();
+    for (i, signature) in signatures.iter().enumerate() {
+        let Some((other, message)) = signatures[..i]
+            .iter()
+            .find_map(|other| Some((other, signature.collision(other)?)))
+        else {
+            continue;
+        };
+        errors.push(syn::Error::new_spanned(&signature.attr, message));
+        errors.push(syn::Error::new_spanned(
+            &other.attr,
+            "previous route defined here",
+        ));
+    }
 
-    // let mut state_type = None;
-    // let mut base_path = None;
-    // let mut middlewares = Vec::new();
+    parsing::combine_errors(errors)
+}
 
-    // todo!()
-    // return item;
-    TokenStream::new()
+/// Combines the generics of an impl block with the generics of one of its functions, keeping
+/// the lifetimes in front of the other parameters.
+fn merge_generics(impl_generics: &Generics, fn_generics: &Generics) -> Generics {
+    let (lifetimes, params): (Vec<_>, Vec<_>) = impl_generics
+        .params
+        .iter()
+        .chain(&fn_generics.params)
+        .cloned()
+        .partition(|param| matches!(param, GenericParam::Lifetime(_)));
+    let predicates = impl_generics
+        .where_clause
+        .iter()
+        .chain(&fn_generics.where_clause)
+        .flat_map(|where_clause| where_clause.predicates.iter().cloned())
+        .collect::<Punctuated<WherePredicate, Comma>>();
+    Generics {
+        lt_token: Some(Default::default()),
+        params: lifetimes.into_iter().chain(params).collect(),
+        gt_token: Some(Default::default()),
+        where_clause: (!predicates.is_empty()).then(|| parse_quote!(where #predicates)),
+    }
+}
+
+/// Replaces every `Self` in `tokens` with `self_ty`.
+fn replace_self(tokens: TokenStream2, self_ty: &Type) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => quote_spanned!(ident.span()=> #self_ty),
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), replace_self(group.stream(), self_ty));
+                new_group.set_span(group.span());
+                quote!(#new_group)
+            }
+            token => quote!(#token),
+        })
+        .collect()
+}
+
+/// Whether the attribute is a `#[route(..)]` or `#[api_route(..)]` attribute.
+fn is_route_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "route" || segment.ident == "api_route")
+}
+
+/// Whether the attribute is an `#[api_route(..)]` attribute.
+fn is_api_route_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "api_route")
+}
+
+/// Prefixes `path` with the controller's `base` path.
+fn join_paths(base: &str, path: &str) -> String {
+    let base = base.trim_end_matches('/');
+    if path == "/" && !base.is_empty() {
+        base.to_string()
+    } else {
+        format!("{base}{path}")
+    }
 }
@@ -1,23 +1,24 @@
-use core::panic;
-
 use quote::ToTokens;
 use syn::{
     token::{Brace, Star},
-    Attribute, Expr, ExprClosure, Lit, LitBool, LitInt,
+    Attribute, Expr, ExprLit, ExprRange, ExprUnary, Lit, LitInt, Pat, PatType, UnOp,
 };
 
 use super::*;
+use crate::compilation::{is_collection, is_option};
 
 struct RouteParser {
     path_params: Vec<(Slash, PathParam)>,
-    query_params: Vec<Ident>,
+    query_params: Vec<QueryParam>,
+    constraints: Vec<PathConstraint>,
+    warnings: Vec<Warning>,
 }
 
 impl RouteParser {
     fn new(lit: LitStr) -> syn::Result<Self> {
         let val = lit.value();
         let span = lit.span();
-        let split_route = val.split('?').collect::<Vec<_>>();
+        let split_route = split_outside_constraints(&val, '?');
         if split_route.len() > 2 {
             return Err(syn::Error::new(span, "expected at most one '?'"));
         }
@@ -29,12 +30,46 @@ impl RouteParser {
         let path = path.strip_prefix('/').unwrap();
 
         let mut path_params = Vec::new();
+        let mut constraints = Vec::new();
+        let mut warnings = Vec::new();
         #[allow(clippy::never_loop)]
-        for path_param in path.split('/') {
-            path_params.push((
-                Slash(span),
-                PathParam::new(path_param, span, Box::new(parse_quote!(()))),
-            ));
+        for path_param in split_outside_constraints(path, '/') {
+            let (segment, constraint) = split_constraint(path_param);
+            let is_capture = segment.starts_with('{') && segment.ends_with('}');
+            if !is_capture && (segment.contains('{') || segment.contains('}')) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("capture `{path_param}` must span a whole path segment"),
+                ));
+            }
+            if cfg!(feature = "legacy-path-warnings") && PathParam::is_legacy(path_param) {
+                let replacement = match path_param.strip_prefix(':') {
+                    Some(name) => format!("{{{name}}}"),
+                    None => format!("{{{path_param}}}"),
+                };
+                warnings.push(Warning {
+                    name: "LEGACY_PATH_SYNTAX",
+                    message: format!(
+                        "`{path_param}` is legacy syntax, use `{replacement}` instead"
+                    ),
+                    span,
+                });
+            }
+            let path_param = PathParam::new(&segment, span, Box::new(parse_quote!(())))?;
+            if let Some(constraint) = constraint {
+                // Wildcards capture the rest of the path, which a constraint can't describe
+                let PathParam::Capture(_, _, ident, _, _) = &path_param else {
+                    return Err(syn::Error::new(
+                        span,
+                        format!(
+                            "constraint `<{constraint}>` on `{segment}` is not allowed, \
+                             constraints are only allowed on `:name`/`{{name}}` captures"
+                        ),
+                    ));
+                };
+                constraints.push(PathConstraint::new(ident.clone(), constraint, span)?);
+            }
+            path_params.push((Slash(span), path_param));
         }
 
         let path_param_len = path_params.len();
@@ -64,13 +99,312 @@ impl RouteParser {
         if split_route.len() == 2 {
             let query = split_route[1];
             for query_param in query.split('&') {
-                query_params.push(Ident::new(query_param, span));
+                if query_param.trim().is_empty() {
+                    return Err(syn::Error::new(
+                        span,
+                        "expected a query parameter after '?' or '&'",
+                    ));
+                }
+                let tokens = query_param.parse::<TokenStream2>().map_err(|_| {
+                    syn::Error::new(span, format!("invalid query parameter `{query_param}`"))
+                })?;
+                query_params.push(syn::parse2::<QueryParam>(respan(tokens, span))?);
             }
         }
 
         Ok(Self {
             path_params,
             query_params,
+            constraints,
+            warnings,
+        })
+    }
+}
+
+/// Splits `str` at every `separator`, except inside the angle brackets of a constraint.
+fn split_outside_constraints(str: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in str.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            c if c == separator && depth == 0 => {
+                parts.push(&str[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&str[start..]);
+    parts
+}
+
+/// Splits the constraint off a capture, such as `{id<u32>}` into `{id}` and `u32`.
+fn split_constraint(segment: &str) -> (String, Option<&str>) {
+    let (capture, closing) = match segment.strip_suffix('}') {
+        Some(capture) if segment.starts_with('{') => (capture, "}"),
+        _ if PathParam::is_legacy(segment) => (segment, ""),
+        _ => return (segment.to_string(), None),
+    };
+    match capture
+        .strip_suffix('>')
+        .and_then(|capture| capture.split_once('<'))
+    {
+        Some((name, constraint)) => (format!("{name}{closing}"), Some(constraint)),
+        None => (segment.to_string(), None),
+    }
+}
+
+/// A constraint on a path capture, written in angle brackets after its name. A request whose
+/// capture doesn't satisfy the constraint is answered with `404 Not Found`.
+pub struct PathConstraint {
+    pub ident: Ident,
+    pub kind: ConstraintKind,
+}
+
+pub enum ConstraintKind {
+    /// The capture must parse as this type, such as `{id<u32>}`.
+    Type(Box<Type>),
+    /// The capture must be an integer in this range, such as `{page<1..=100>}`.
+    Range(Box<ExprRange>),
+    /// The capture must match this regex as a whole, such as `{slug<[a-z-]+>}`. The pattern
+    /// is anchored with `^(?:` and `)$`.
+    Regex(LitStr),
+}
+
+impl PathConstraint {
+    /// Parses the constraint `str` of the capture `ident`.
+    ///
+    /// A single type path is a type constraint, and a range is a range constraint. Everything
+    /// else is a regex, which is validated here.
+    fn new(ident: Ident, str: &str, span: Span) -> syn::Result<Self> {
+        if str.trim().is_empty() {
+            return Err(syn::Error::new(
+                span,
+                format!("expected a constraint for `{ident}`"),
+            ));
+        }
+        if let Ok(tokens) = str.parse::<TokenStream2>() {
+            let tokens = respan(tokens, span);
+            if let Ok(ty @ Type::Path(_)) = syn::parse2::<Type>(tokens.clone()) {
+                return Ok(Self {
+                    ident,
+                    kind: ConstraintKind::Type(Box::new(ty)),
+                });
+            }
+            if let Ok(range) = syn::parse2::<ExprRange>(tokens) {
+                // A range without bounds, such as `..`, would allow every capture
+                let bounds = [&range.start, &range.end];
+                if bounds.iter().all(|bound| bound.is_none())
+                    || !bounds
+                        .iter()
+                        .all(|bound| bound.as_deref().is_none_or(is_int_lit))
+                {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("expected integer bounds in the range constraint of `{ident}`"),
+                    ));
+                }
+                return Ok(Self {
+                    ident,
+                    kind: ConstraintKind::Range(Box::new(range)),
+                });
+            }
+        }
+
+        let pattern = format!("^(?:{str})$");
+        regex_lite::Regex::new(&pattern).map_err(|err| {
+            syn::Error::new(
+                span,
+                format!("invalid regex constraint `{str}` for `{ident}`: {err}"),
+            )
+        })?;
+        Ok(Self {
+            ident,
+            kind: ConstraintKind::Regex(LitStr::new(&pattern, span)),
+        })
+    }
+}
+
+/// Whether `expr` is an integer literal, such as `1` or `-1`.
+fn is_int_lit(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(_), ..
+        }) => true,
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => is_int_lit(expr),
+        _ => false,
+    }
+}
+
+/// A warning about the usage of the macros.
+///
+/// Proc macros can't emit warnings on stable Rust, so the warning is emitted as the use of a
+/// deprecated constant `name`, with `message` as its deprecation note.
+pub struct Warning {
+    pub name: &'static str,
+    pub message: String,
+    pub span: Span,
+}
+
+impl ToTokens for Warning {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let name = Ident::new(self.name, self.span);
+        let message = &self.message;
+        tokens.extend(quote_spanned! {self.span=>
+            {
+                #[deprecated(note = #message)]
+                const #name: () = ();
+                #[allow(clippy::let_unit_value)]
+                let _ = #name;
+            }
+        });
+    }
+}
+
+/// Sets the span of every token in `tokens` to `span`.
+fn respan(tokens: TokenStream2, span: Span) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut new_group = Group::new(group.delimiter(), respan(group.stream(), span));
+                new_group.set_span(span);
+                token = TokenTree::Group(new_group);
+            }
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+/// A query parameter of a route, written as `ident`, optionally followed by `as "name"` to
+/// rename the parameter in the query, and by `= default` to make it optional.
+pub struct QueryParam {
+    pub ident: Ident,
+    pub ty: Box<Type>,
+    pub rename: Option<LitStr>,
+    pub default: Option<Expr>,
+}
+
+impl QueryParam {
+    /// The name of the parameter in the query.
+    pub fn name(&self) -> String {
+        match &self.rename {
+            Some(rename) => rename.value(),
+            None => self.ident.to_string(),
+        }
+    }
+
+    /// Whether the parameter can be left out of the query.
+    pub fn is_optional(&self) -> bool {
+        self.default.is_some() || is_option(&self.ty) || is_collection(&self.ty)
+    }
+}
+
+impl Parse for QueryParam {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        let rename = match input.parse::<Option<Token![as]>>()? {
+            Some(_) => Some(input.parse::<LitStr>()?),
+            None => None,
+        };
+        let default = match input.parse::<Option<Token![=]>>()? {
+            Some(_) => Some(input.parse::<Expr>()?),
+            None => None,
+        };
+        Ok(Self {
+            ident,
+            ty: Box::new(parse_quote!(())),
+            rename,
+            default,
+        })
+    }
+}
+
+/// A header parameter of a route, written as `ident`, optionally followed by `= "Header-Name"`.
+/// Without a name, the header is named after the identifier, with `_` replaced by `-`.
+pub struct HeaderParam {
+    pub ident: Ident,
+    pub ty: Box<Type>,
+    pub name: Option<LitStr>,
+}
+
+impl HeaderParam {
+    /// The name of the header.
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.value(),
+            None => self.ident.to_string().replace('_', "-"),
+        }
+    }
+}
+
+impl Parse for HeaderParam {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        let name = match input.parse::<Option<Token![=]>>()? {
+            Some(_) => Some(input.parse::<LitStr>()?),
+            None => None,
+        };
+        if let Some(name) = &name {
+            check_token(name, "header")?;
+        }
+        Ok(Self {
+            ident,
+            ty: Box::new(parse_quote!(())),
+            name,
+        })
+    }
+}
+
+/// Checks that `name` is a valid header or cookie name, a token as defined by RFC 9110.
+fn check_token(name: &LitStr, what: &str) -> syn::Result<()> {
+    let is_token = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+    if name.value().is_empty() || !name.value().chars().all(is_token) {
+        return Err(syn::Error::new(name.span(), format!("invalid {what} name")));
+    }
+    Ok(())
+}
+
+/// A cookie parameter of a route, written as `ident`, optionally followed by `= "cookie-name"`.
+/// Without a name, the cookie is named after the identifier.
+pub struct CookieParam {
+    pub ident: Ident,
+    pub ty: Box<Type>,
+    pub name: Option<LitStr>,
+}
+
+impl CookieParam {
+    /// The name of the cookie.
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.value(),
+            None => self.ident.to_string(),
+        }
+    }
+}
+
+impl Parse for CookieParam {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        let name = match input.parse::<Option<Token![=]>>()? {
+            Some(_) => Some(input.parse::<LitStr>()?),
+            None => None,
+        };
+        if let Some(name) = &name {
+            check_token(name, "cookie")?;
+        }
+        Ok(Self {
+            ident,
+            ty: Box::new(parse_quote!(())),
+            name,
         })
     }
 }
@@ -102,30 +436,59 @@ impl PathParam {
         }
     }
 
-    fn new(str: &str, span: Span, ty: Box<Type>) -> Self {
-        if str.starts_with(':') {
-            let str = str.strip_prefix(':').unwrap();
-            Self::Capture(
-                LitStr::new(str, span),
-                Brace(span),
-                Ident::new(str, span),
-                ty,
-                Brace(span),
-            )
+    fn new(str: &str, span: Span, ty: Box<Type>) -> syn::Result<Self> {
+        if let Some(inner) = str.strip_prefix('{').and_then(|str| str.strip_suffix('}')) {
+            match inner.strip_prefix('*') {
+                Some(str) => Self::new_wildcard(str, span, ty),
+                None => Self::new_capture(inner, span, ty),
+            }
+        } else if str.starts_with(':') {
+            Self::new_capture(str.strip_prefix(':').unwrap(), span, ty)
         } else if str.starts_with('*') && str.len() > 1 {
-            let str = str.strip_prefix('*').unwrap();
-            Self::WildCard(
-                LitStr::new(str, span),
-                Brace(span),
-                Star(span),
-                Ident::new(str, span),
-                ty,
-                Brace(span),
-            )
+            Self::new_wildcard(str.strip_prefix('*').unwrap(), span, ty)
         } else {
-            Self::Static(LitStr::new(str, span))
+            Ok(Self::Static(LitStr::new(str, span)))
         }
     }
+
+    /// Whether `str` is a capture in the legacy `:id` or `*rest` syntax.
+    fn is_legacy(str: &str) -> bool {
+        str.starts_with(':') || (str.starts_with('*') && str.len() > 1)
+    }
+
+    fn new_capture(str: &str, span: Span, ty: Box<Type>) -> syn::Result<Self> {
+        Ok(Self::Capture(
+            LitStr::new(str, span),
+            Brace(span),
+            capture_ident(str, span)?,
+            ty,
+            Brace(span),
+        ))
+    }
+
+    fn new_wildcard(str: &str, span: Span, ty: Box<Type>) -> syn::Result<Self> {
+        Ok(Self::WildCard(
+            LitStr::new(str, span),
+            Brace(span),
+            Star(span),
+            capture_ident(str, span)?,
+            ty,
+            Brace(span),
+        ))
+    }
+}
+
+/// The identifier of the capture named `str`, which must be a valid identifier, since it's
+/// bound to an argument of the handler.
+fn capture_ident(str: &str, span: Span) -> syn::Result<Ident> {
+    match syn::parse_str::<Ident>(str) {
+        Ok(_) => Ok(Ident::new(str, span)),
+        Err(_) if str.is_empty() => Err(syn::Error::new(span, "expected a capture name")),
+        Err(_) => Err(syn::Error::new(
+            span,
+            format!("invalid capture name `{str}`, expected an identifier"),
+        )),
+    }
 }
 
 pub struct Security(pub Vec<(LitStr, StrArray)>);
@@ -147,8 +510,8 @@ impl Parse for Security {
     }
 }
 
-impl ToString for Security {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Security {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = String::new();
         s.push('{');
         for (i, (scheme, scopes)) in self.0.iter().enumerate() {
@@ -160,7 +523,7 @@ impl ToString for Security {
             s.push_str(&scopes.to_string());
         }
         s.push('}');
-        s
+        f.write_str(&s)
     }
 }
 
@@ -183,8 +546,8 @@ impl Parse for Responses {
     }
 }
 
-impl ToString for Responses {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Responses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = String::new();
         s.push('{');
         for (i, (status, ty)) in self.0.iter().enumerate() {
@@ -196,7 +559,7 @@ impl ToString for Responses {
             s.push_str(&ty.to_token_stream().to_string());
         }
         s.push('}');
-        s
+        f.write_str(&s)
     }
 }
 
@@ -215,8 +578,8 @@ impl Parse for StrArray {
     }
 }
 
-impl ToString for StrArray {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for StrArray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = String::new();
         s.push('[');
         for (i, lit) in self.0.iter().enumerate() {
@@ -228,52 +591,431 @@ impl ToString for StrArray {
             s.push('"');
         }
         s.push(']');
-        s
+        f.write_str(&s)
     }
 }
 
-fn doc_iter(attrs: &[Attribute]) -> impl Iterator<Item = &LitStr> + '_ {
-    attrs
-        .iter()
-        .filter(|attr| attr.path().is_ident("doc"))
-        .map(|attr| {
-            let Meta::NameValue(meta) = &attr.meta else {
-                panic!("doc attribute is not a name-value attribute");
+/// The kind of parameter an argument of a handler is bound to with an attribute.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    Path,
+    Query,
+    Header,
+    Body,
+}
+
+impl ArgKind {
+    const ALL: [Self; 4] = [Self::Path, Self::Query, Self::Header, Self::Body];
+
+    /// The name of the attribute, such as `path` for `#[path]`.
+    pub fn attr_name(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Query => "query",
+            Self::Header => "header",
+            Self::Body => "body",
+        }
+    }
+
+    /// The parameters of this kind in error messages, such as `path parameter`.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Path => "path parameter",
+            Self::Query => "query parameter",
+            Self::Header => "header",
+            Self::Body => "body",
+        }
+    }
+}
+
+/// An argument of a handler that declares where it's extracted from with an attribute, such as
+/// `#[path] UserId(id): UserId`, `#[query("page")] Page(p): Page`,
+/// `#[header("X-Request-Id")] request_id: u32` or `#[body] Json(item): Json<Item>`.
+pub enum ArgBinding {
+    /// Bound to the path capture with this identifier.
+    Path(Ident),
+    /// Bound to the query parameter with this identifier, which is added to the query if the
+    /// route doesn't declare it.
+    Query(Ident),
+    /// Bound to the header parameter with this identifier, which is added to the headers of the
+    /// route if it doesn't declare it, optionally with an explicit header name.
+    Header(Ident, Option<LitStr>),
+    /// The request body.
+    Body,
+}
+
+impl ArgBinding {
+    /// Parses the binding attribute of `arg`, if it has one.
+    ///
+    /// Without an explicit name, the argument is bound to the parameter with the name of the
+    /// single identifier in its pattern.
+    pub fn from_arg(arg: &PatType) -> syn::Result<Option<Self>> {
+        let mut binding = None;
+        for attr in &arg.attrs {
+            let Some(kind) = ArgKind::ALL
+                .into_iter()
+                .find(|kind| attr.path().is_ident(kind.attr_name()))
+            else {
+                continue;
             };
-            let Expr::Lit(lit) = &meta.value else {
-                panic!("doc attribute is not a string literal");
+            if binding.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "an argument can only be bound to a single parameter",
+                ));
+            }
+            let inferred_name = || {
+                single_binding(&arg.pat).ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &arg.pat,
+                        format!(
+                            "can't infer the {} of this pattern, name it with `#[{}(\"name\")]`",
+                            kind.describe(),
+                            kind.attr_name()
+                        ),
+                    )
+                })
             };
-            let Lit::Str(lit_str) = &lit.lit else {
-                panic!("doc attribute is not a string literal");
+            let explicit_ident = || {
+                let lit = attr.parse_args::<LitStr>()?;
+                let ident = syn::parse_str::<Ident>(&lit.value())
+                    .map_err(|_| syn::Error::new(lit.span(), "expected an identifier"))?;
+                Ok::<_, syn::Error>(Ident::new(&ident.to_string(), lit.span()))
             };
-            lit_str
+            binding = Some(match (kind, &attr.meta) {
+                (ArgKind::Body, Meta::Path(_)) => Self::Body,
+                (ArgKind::Body, _) => {
+                    return Err(syn::Error::new_spanned(attr, "expected `#[body]`"));
+                }
+                (_, Meta::NameValue(_)) => {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!("expected `#[{0}]` or `#[{0}(\"name\")]`", kind.attr_name()),
+                    ))
+                }
+                (ArgKind::Path, Meta::Path(_)) => Self::Path(inferred_name()?),
+                (ArgKind::Path, Meta::List(_)) => Self::Path(explicit_ident()?),
+                (ArgKind::Query, Meta::Path(_)) => Self::Query(inferred_name()?),
+                (ArgKind::Query, Meta::List(_)) => Self::Query(explicit_ident()?),
+                (ArgKind::Header, Meta::Path(_)) => Self::Header(inferred_name()?, None),
+                (ArgKind::Header, Meta::List(_)) => {
+                    let name = attr.parse_args::<LitStr>()?;
+                    check_token(&name, "header")?;
+                    // The header is passed to the handler by this identifier
+                    let ident = single_binding(&arg.pat).unwrap_or_else(|| {
+                        let name = name.value().replace(|c: char| !c.is_alphanumeric(), "_");
+                        format_ident!("__header_{}__", name)
+                    });
+                    Self::Header(ident, Some(name))
+                }
+            });
+        }
+        Ok(binding)
+    }
+
+    /// The kind of parameter the argument is bound to.
+    pub fn kind(&self) -> ArgKind {
+        match self {
+            Self::Path(_) => ArgKind::Path,
+            Self::Query(_) => ArgKind::Query,
+            Self::Header(_, _) => ArgKind::Header,
+            Self::Body => ArgKind::Body,
+        }
+    }
+
+    /// Whether `attr` is a binding attribute, which is removed from the handler.
+    pub fn is_binding_attr(attr: &Attribute) -> bool {
+        ArgKind::ALL
+            .into_iter()
+            .any(|kind| attr.path().is_ident(kind.attr_name()))
+    }
+}
+
+/// The identifier bound by `pat`, if it binds exactly one, such as `id` in `UserId(id)`.
+fn single_binding(pat: &Pat) -> Option<Ident> {
+    fn collect<'a>(pat: &'a Pat, idents: &mut Vec<&'a Ident>) {
+        match pat {
+            Pat::Ident(pat) => {
+                idents.push(&pat.ident);
+                if let Some((_at, pat)) = &pat.subpat {
+                    collect(pat, idents);
+                }
+            }
+            Pat::Paren(pat) => collect(&pat.pat, idents),
+            Pat::Reference(pat) => collect(&pat.pat, idents),
+            Pat::Type(pat) => collect(&pat.pat, idents),
+            Pat::Tuple(pat) => pat.elems.iter().for_each(|pat| collect(pat, idents)),
+            Pat::TupleStruct(pat) => pat.elems.iter().for_each(|pat| collect(pat, idents)),
+            Pat::Slice(pat) => pat.elems.iter().for_each(|pat| collect(pat, idents)),
+            Pat::Struct(pat) => pat
+                .fields
+                .iter()
+                .for_each(|field| collect(&field.pat, idents)),
+            _ => {}
+        }
+    }
+
+    let mut idents = Vec::new();
+    collect(pat, &mut idents);
+    match idents.as_slice() {
+        [ident] => Some((*ident).clone()),
+        _ => None,
+    }
+}
+
+/// Removes the binding attributes, such as `#[path]`, from the arguments of `sig`, since they
+/// are not valid outside of the macros.
+pub fn strip_binding_attrs(sig: &mut Signature) {
+    for arg in &mut sig.inputs {
+        if let FnArg::Typed(arg) = arg {
+            arg.attrs.retain(|attr| !ArgBinding::is_binding_attr(attr));
+        }
+    }
+}
+
+/// The string literals of the doc attributes in `attrs`.
+///
+/// Doc attributes that aren't string literals, such as `#[doc(hidden)]` or
+/// `#[doc = include_str!("..")]`, are skipped.
+pub fn doc_iter(attrs: &[Attribute]) -> impl Iterator<Item = &LitStr> + '_ {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) => Some(lit_str),
+                _ => None,
+            },
+            _ => None,
         })
 }
 
+/// Combines `errors` into a single error, so that they are all reported at once.
+pub fn combine_errors(errors: impl IntoIterator<Item = syn::Error>) -> syn::Result<()> {
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|other| error.combine(other));
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Appends a suggestion to `message` if one of `candidates` is similar to `name`, such as
+/// `middleware` for `midleware`.
+pub fn with_suggestion<'a>(
+    message: String,
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> String {
+    match suggestion(name, candidates) {
+        Some(candidate) => format!("{message}, did you mean `{candidate}`?"),
+        None => message,
+    }
+}
+
+/// The one of `candidates` that is most similar to `name`, unless none of them are similar.
+fn suggestion<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| {
+            *distance > 0 && *distance <= (name.len().max(candidate.len()) / 3).max(1)
+        })
+        .min_by_key(|(distance, _candidate)| *distance)
+        .map(|(_distance, candidate)| candidate)
+}
+
+/// The edit distance between `a` and `b`, counting insertions, deletions, substitutions and
+/// transpositions of adjacent characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 0..=a.len() {
+        for j in 0..=b.len() {
+            distances[i][j] = match (i, j) {
+                (0, j) => j,
+                (i, 0) => i,
+                (i, j) => {
+                    let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+                    let distance = substitution
+                        .min(distances[i - 1][j] + 1)
+                        .min(distances[i][j - 1] + 1);
+                    if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                        distance.min(distances[i - 2][j - 2] + 1)
+                    } else {
+                        distance
+                    }
+                }
+            };
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+/// Skips the value of an invalid route option, up to the next `,`.
+fn skip_option_value(input: ParseStream) -> syn::Result<()> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        while let Some((token, next)) = rest.token_tree() {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == ',' => break,
+                _ => rest = next,
+            }
+        }
+        Ok(((), rest))
+    })
+}
+
 pub struct Route {
-    pub method: Method,
+    pub methods: Vec<Method>,
     pub path_params: Vec<(Slash, PathParam)>,
-    pub query_params: Vec<Ident>,
+    pub query_params: Vec<QueryParam>,
+    pub constraints: Vec<PathConstraint>,
+    pub headers: Vec<HeaderParam>,
+    pub cookies: Vec<CookieParam>,
+    pub body: Option<Type>,
     pub state: Option<Type>,
+    /// Whether to generate a function that builds the url of the route.
+    pub url: bool,
+    /// Whether to generate functions that start a request to the route.
+    pub client: bool,
+    pub warnings: Vec<Warning>,
     pub route_lit: LitStr,
+    pub responses: Option<Responses>,
+    pub security: Option<Security>,
+    pub tags: Option<StrArray>,
 }
 
 impl Parse for Route {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let method = input.parse::<Method>()?;
+        let methods = Punctuated::<Method, Token![|]>::parse_separated_nonempty(input)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut errors = Vec::new();
+        for (i, method) in methods.iter().enumerate() {
+            if methods[..i]
+                .iter()
+                .any(|other| other.as_http_str() == method.as_http_str())
+            {
+                errors.push(syn::Error::new(
+                    method.span(),
+                    format!("duplicate method `{}`", method.as_http_str()),
+                ));
+            }
+        }
         let route_lit = input.parse::<LitStr>()?;
-        let route_parser = RouteParser::new(route_lit.clone())?;
+        // Parsing continues after an invalid path, to report the errors in the options as well
+        let route_parser = RouteParser::new(route_lit.clone())
+            .map_err(|err| errors.push(err))
+            .ok();
         let state = match input.parse::<kw::with>() {
             Ok(_) => Some(input.parse::<Type>()?),
             Err(_) => None,
         };
 
+        let mut responses = None;
+        let mut security = None;
+        let mut tags = None;
+        let mut headers = None;
+        let mut cookies = None;
+        let mut body = None;
+        let mut url = false;
+        let mut client = false;
+        while input.parse::<Token![,]>().is_ok() && !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            let duplicate = match ident.to_string().as_str() {
+                "headers" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let params = content.parse_terminated(HeaderParam::parse, Token![,])?;
+                    headers.replace(params.into_iter().collect()).is_some()
+                }
+                "cookies" if !cfg!(feature = "cookies") => {
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        "cookie parameters require the `cookies` feature of `axum-controller`",
+                    ));
+                    skip_option_value(input)?;
+                    false
+                }
+                "cookies" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let params = content.parse_terminated(CookieParam::parse, Token![,])?;
+                    cookies.replace(params.into_iter().collect()).is_some()
+                }
+                "url" => std::mem::replace(&mut url, true),
+                "client" if !cfg!(feature = "client") => {
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        "request functions require the `client` feature of `axum-controller`",
+                    ));
+                    false
+                }
+                "client" => std::mem::replace(&mut client, true),
+                "body" | "responses" | "security" | "tags" => {
+                    let _ = input.parse::<Token![=]>()?;
+                    match ident.to_string().as_str() {
+                        "body" => body.replace(input.parse::<Type>()?).is_some(),
+                        "responses" => responses.replace(input.parse::<Responses>()?).is_some(),
+                        "security" => security.replace(input.parse::<Security>()?).is_some(),
+                        _ => tags.replace(input.parse::<StrArray>()?).is_some(),
+                    }
+                }
+                key => {
+                    const OPTIONS: &[&str] = &[
+                        "body",
+                        "client",
+                        "cookies",
+                        "headers",
+                        "responses",
+                        "security",
+                        "tags",
+                        "url",
+                    ];
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        with_suggestion(
+                            format!("expected one of ({})", OPTIONS.join(", ")),
+                            key,
+                            OPTIONS.iter().copied(),
+                        ),
+                    ));
+                    skip_option_value(input)?;
+                    false
+                }
+            };
+            if duplicate {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    format!("duplicate `{ident}` option"),
+                ));
+            }
+        }
+        combine_errors(errors)?;
+        let route_parser = route_parser.expect("invalid paths are reported as errors");
+
         Ok(Route {
-            method,
+            methods,
             path_params: route_parser.path_params,
             query_params: route_parser.query_params,
+            constraints: route_parser.constraints,
+            warnings: route_parser.warnings,
+            headers: headers.unwrap_or_default(),
+            cookies: cookies.unwrap_or_default(),
+            body,
             state,
+            url,
+            client,
             route_lit,
+            responses,
+            security,
+            tags,
         })
     }
 }
@@ -287,37 +1029,149 @@ pub enum Method {
     Connect(Span),
     Options(Span),
     Trace(Span),
+    Patch(Span),
+    /// A method without a dedicated axum routing function, such as `PROPFIND`, written as an
+    /// identifier or as a string literal.
+    Custom(LitStr),
+}
+
+impl Method {
+    const STANDARD: &[&str] = &[
+        "GET", "POST", "PUT", "DELETE", "HEAD", "CONNECT", "OPTIONS", "TRACE", "PATCH",
+    ];
+
+    /// The standard method `name`, ignoring case.
+    fn standard(name: &str, span: Span) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "GET" => Some(Self::Get(span)),
+            "POST" => Some(Self::Post(span)),
+            "PUT" => Some(Self::Put(span)),
+            "DELETE" => Some(Self::Delete(span)),
+            "HEAD" => Some(Self::Head(span)),
+            "CONNECT" => Some(Self::Connect(span)),
+            "OPTIONS" => Some(Self::Options(span)),
+            "TRACE" => Some(Self::Trace(span)),
+            "PATCH" => Some(Self::Patch(span)),
+            _ => None,
+        }
+    }
 }
 
 impl Parse for Method {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // A string literal is taken exactly, as an escape hatch for custom methods that look
+        // like a typo, or that aren't identifiers, such as `"M-SEARCH"`
+        if input.peek(LitStr) {
+            let lit = input.parse::<LitStr>()?;
+            if lit.value().starts_with('/') {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected a method before the path",
+                ));
+            }
+            check_token(&lit, "method")?;
+            return Ok(Self::standard(&lit.value(), lit.span())
+                .filter(|method| method.as_http_str() == lit.value())
+                .unwrap_or(Self::Custom(lit)));
+        }
+
         let ident = input.parse::<Ident>()?;
-        match ident.to_string().to_uppercase().as_str() {
-            "GET" => Ok(Self::Get(ident.span())),
-            "POST" => Ok(Self::Post(ident.span())),
-            "PUT" => Ok(Self::Put(ident.span())),
-            "DELETE" => Ok(Self::Delete(ident.span())),
-            "HEAD" => Ok(Self::Head(ident.span())),
-            "CONNECT" => Ok(Self::Connect(ident.span())),
-            "OPTIONS" => Ok(Self::Options(ident.span())),
-            "TRACE" => Ok(Self::Trace(ident.span())),
-            _ => Err(input
-                .error("expected one of (GET, POST, PUT, DELETE, HEAD, CONNECT, OPTIONS, TRACE)")),
+        if let Some(method) = Self::standard(&ident.to_string(), ident.span()) {
+            return Ok(method);
+        }
+        let method = ident.to_string();
+        // HTTP methods are case-sensitive, so custom methods must be written exactly. A
+        // method close to a standard one, such as `GTE`, is more likely a typo.
+        if method == method.to_uppercase()
+            && suggestion(&method, Self::STANDARD.iter().copied()).is_none()
+        {
+            return Ok(Self::Custom(LitStr::new(&method, ident.span())));
         }
+        let mut message = with_suggestion(
+            format!(
+                "expected one of ({}), or a custom method in uppercase",
+                Self::STANDARD.join(", ")
+            ),
+            &method,
+            Self::STANDARD.iter().copied(),
+        );
+        if method == method.to_uppercase() {
+            message.push_str(&format!(
+                " (write `\"{method}\"` to route the custom method `{method}`)"
+            ));
+        }
+        Err(syn::Error::new(ident.span(), message))
     }
 }
 
 impl Method {
-    pub fn to_axum_method_name(&self) -> Ident {
+    /// The axum routing function of this method, such as `get`, or `None` for custom methods.
+    pub fn to_axum_method_name(&self) -> Option<Ident> {
+        let name = match self {
+            Self::Get(_) => "get",
+            Self::Post(_) => "post",
+            Self::Put(_) => "put",
+            Self::Delete(_) => "delete",
+            Self::Head(_) => "head",
+            Self::Connect(_) => "connect",
+            Self::Options(_) => "options",
+            Self::Trace(_) => "trace",
+            Self::Patch(_) => "patch",
+            Self::Custom(_) => return None,
+        };
+        Some(Ident::new(name, self.span()))
+    }
+
+    /// The name of the method as sent over the wire, such as `GET`.
+    pub fn as_http_str(&self) -> String {
+        match self.to_axum_method_name() {
+            Some(name) => name.to_string().to_uppercase(),
+            None => match self {
+                Self::Custom(lit) => lit.value(),
+                _ => unreachable!("only custom methods have no routing function"),
+            },
+        }
+    }
+
+    /// The `axum::routing::MethodFilter` of this method, or `None` for custom methods.
+    pub fn to_method_filter(&self) -> Option<TokenStream2> {
+        match self {
+            Self::Custom(_) => None,
+            _ => {
+                let filter = Ident::new(&self.as_http_str(), self.span());
+                Some(quote! { ::axum::routing::MethodFilter::#filter })
+            }
+        }
+    }
+
+    /// The `utoipa::openapi::HttpMethod` of this method, or `None` if OpenAPI doesn't support it.
+    pub fn to_openapi_method(&self) -> Option<TokenStream2> {
+        let method = match self {
+            Self::Get(span) => Ident::new("Get", *span),
+            Self::Post(span) => Ident::new("Post", *span),
+            Self::Put(span) => Ident::new("Put", *span),
+            Self::Delete(span) => Ident::new("Delete", *span),
+            Self::Head(span) => Ident::new("Head", *span),
+            Self::Options(span) => Ident::new("Options", *span),
+            Self::Trace(span) => Ident::new("Trace", *span),
+            Self::Patch(span) => Ident::new("Patch", *span),
+            Self::Connect(_) | Self::Custom(_) => return None,
+        };
+        Some(quote! { ::axum_controller::openapi::utoipa::openapi::HttpMethod::#method })
+    }
+
+    pub fn span(&self) -> Span {
         match self {
-            Self::Get(span) => Ident::new("get", *span),
-            Self::Post(span) => Ident::new("post", *span),
-            Self::Put(span) => Ident::new("put", *span),
-            Self::Delete(span) => Ident::new("delete", *span),
-            Self::Head(span) => Ident::new("head", *span),
-            Self::Connect(span) => Ident::new("connect", *span),
-            Self::Options(span) => Ident::new("options", *span),
-            Self::Trace(span) => Ident::new("trace", *span),
+            Self::Get(span)
+            | Self::Post(span)
+            | Self::Put(span)
+            | Self::Delete(span)
+            | Self::Head(span)
+            | Self::Connect(span)
+            | Self::Options(span)
+            | Self::Trace(span)
+            | Self::Patch(span) => *span,
+            Self::Custom(lit) => lit.span(),
         }
     }
 }
@@ -2,7 +2,7 @@
 use axum::extract::{Json, State};
 use axum_controller::{controller, route, TypedRouter};
 
-#[route(GET "/item/:id?amount&offset")]
+#[route(GET "/item/{id}?amount&offset")]
 async fn item_handler(
     id: u32,
     amount: Option<u32>,
@@ -13,13 +13,19 @@ async fn item_handler(
     todo!("handle request")
 }
 
-struct TestController;
+struct ItemController;
 
-#[controller(path = "/asd")]
-impl TestController {}
+#[controller(path = "/items", state = String)]
+impl ItemController {
+    #[route(GET "/{id}")]
+    async fn get(id: u32, State(state): State<String>) -> String {
+        todo!("handle request")
+    }
+}
 
 fn main() {
     let router: axum::Router = axum::Router::new()
         .typed_route(item_handler)
+        .merge(ItemController::into_router())
         .with_state("state".to_string());
 }
@@ -0,0 +1,55 @@
+//! Typed clients for routes, built on [`reqwest`].
+//!
+//! With the `client` feature, the [`route`](crate::route) macro generates a function `request`
+//! in the companion module of every route with the `client` option, e.g.
+//! `#[route(GET "/item/{id}?amount&offset", client)]`, and the
+//! [`controller`](crate::controller) macro generates a `<name>_request` function for these
+//! routes of a controller. The option is separate from `url`, since the request functions need
+//! the body of the route to implement `Serialize`. These functions start a request to the route
+//! on a [`Client`], with the method, url, headers and cookies of the route already set:
+//! ```ignore
+//! let client = Client::new("http://localhost:3000");
+//! let response = item_handler::request(&client, 1, Some(2), None).send().await?;
+//! ```
+//! The arguments are the path- and query-parameters, followed by the headers declared with
+//! `headers(..)` and the cookies declared with `cookies(..)`, which are written with `Display`.
+//! Optional headers and cookies are left out when they are `None`. Routes with a `Json<T>`,
+//! `Form<T>` or `String` body take the body as their last argument.
+//! Routes with multiple methods get a function per method, such as `request_put` and
+//! `request_patch` for `PUT | PATCH`.
+//! The returned [`reqwest::RequestBuilder`] can be used to add headers, other bodies, etc.
+
+pub use reqwest;
+
+/// A client for typed routes, served at `base_url`.
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl Client {
+    /// Creates a client for the routes served at `base_url`, e.g. `http://localhost:3000`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_client(reqwest::Client::new(), base_url)
+    }
+
+    /// Creates a client for the routes served at `base_url`, which sends its requests with
+    /// the given [`reqwest::Client`].
+    pub fn with_client(http: reqwest::Client, base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        base_url.truncate(base_url.trim_end_matches('/').len());
+        Self { http, base_url }
+    }
+
+    /// The url the routes are served at.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Starts a request to `url`, relative to the base url of this client.
+    pub fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.base_url, url))
+    }
+}
@@ -21,18 +21,97 @@
 //!
 //! By marking the `amount` and `offset` parameters as `Option<T>`, they become optional.
 //!
+//! ## Controllers
+//! Routes can be grouped in the impl block of a type with the [`controller`] macro. All
+//! [`route`]-annotated methods are registered under the controller's base path, in the router
+//! returned by the generated `into_router` function. In the example above, `ItemController::get`
+//! is served at `/items/{id}`.
+//!
+//! ## Route tables
+//! A [`TrackedRouter`] wraps an axum router, and keeps a [`RouteTable`] of the typed routes and
+//! controllers added to it. The route table can be printed, e.g. at startup:
+//! ```ignore
+//! let router = TrackedRouter::new()
+//!     .typed_route(item_handler)
+//!     .typed_controller::<ItemController>();
+//! println!("{}", router.route_table());
+//! let router = router.into_router().with_state(state);
+//! ```
+//!
+//! ## Urls and clients
+//! Every route gets a companion module with the name of the handler. With the `url` option, e.g.
+//! `#[route(GET "/item/{id}?amount&offset", url)]`, it contains a function `url` which builds
+//! the url of the route from its path- and query-parameters, e.g.
+//! `item_handler::url(1, Some(2), None)` is `/item/1?amount=2`. Controller routes get an
+//! associated `<name>_url` function instead.
+//!
+//! With the `client` feature and the `client` option, a `request` (or `<name>_request`) function
+//! is generated as well, which starts a request to the route on a [`client::Client`].
+//!
+//! ## Registry
+//! With the `registry` feature, routes and controllers register themselves in a global
+//! [`registry`], and [`TypedRouter::auto_routes`] adds all of them to a router at once.
+//!
+//! ## OpenAPI
+//! With the `openapi` feature, routes created with the [`api_route`] macro are documented with
+//! OpenAPI. Besides the syntax of [`route`], it accepts the `responses`, `security` and `tags`
+//! of the route. These routes are added to an [`openapi::OpenApiRouter`] with
+//! [`openapi::TypedApiRouter::typed_api_route`], which collects them into a single OpenAPI
+//! document. Controllers additionally get an `into_openapi_router` function.
+//!
+//! ```ignore
+//! use axum_controller::{api_route, openapi::{OpenApiRouter, TypedApiRouter}};
+//!
+//! /// Get an item.
+//! #[api_route(GET "/item/{id}", responses = { 200: String }, tags = ["items"])]
+//! async fn item_handler(id: u32) -> String {
+//!     todo!("handle request")
+//! }
+//!
+//! let (router, openapi) = OpenApiRouter::new()
+//!     .typed_api_route(item_handler)
+//!     .split_for_parts();
+//! let json = openapi.to_pretty_json().unwrap();
+//! ```
+//!
 
 use axum::routing::MethodRouter;
 
-type TypedHandler<S = ()> = fn() -> (&'static str, MethodRouter<S>);
-pub use axum_controller_macros::route;
+mod route_info;
+mod route_table;
+
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+#[cfg(feature = "registry")]
+pub mod registry;
+
+type TypedHandler<S = ()> = fn() -> (&'static RouteInfo, MethodRouter<S>);
+#[cfg(feature = "openapi")]
+pub use axum_controller_macros::api_route;
 pub use axum_controller_macros::controller;
+pub use axum_controller_macros::route;
+pub use route_info::{BodyInfo, ParamInfo, RouteInfo};
+pub use route_table::{RouteTable, TrackedRouter};
+
+/// A controller, created with the [`controller`] macro.
+pub trait Controller {
+    /// The state type of the controller.
+    type State: Clone + Send + Sync + 'static;
+
+    /// Creates an axum router containing all routes of this controller.
+    fn into_router() -> axum::Router<Self::State>;
+
+    /// Describes the routes of this controller.
+    fn routes() -> Vec<&'static RouteInfo>;
+}
 
 /// A trait that allows typed routes, created with the [`route`] macro to
 /// be added to an axum router.
 ///
-/// Typed handlers are of the form `fn() -> (&'static str, MethodRouter<S>)`, where
-/// `S` is the state type. The first element of the tuple is the path, and the second
+/// Typed handlers are of the form `fn() -> (&'static RouteInfo, MethodRouter<S>)`, where
+/// `S` is the state type. The first element of the tuple describes the route, and the second
 /// is the method router.
 pub trait TypedRouter: Sized {
     /// The state type of the router.
@@ -40,10 +119,17 @@ pub trait TypedRouter: Sized {
 
     /// Add a typed route to the router, usually created with the [`route`] macro.
     ///
-    /// Typed handlers are of the form `fn() -> (&'static str, MethodRouter<S>)`, where
-    /// `S` is the state type. The first element of the tuple is the path, and the second
+    /// Typed handlers are of the form `fn() -> (&'static RouteInfo, MethodRouter<S>)`, where
+    /// `S` is the state type. The first element of the tuple describes the route, and the second
     /// is the method router.
     fn typed_route(self, handler: TypedHandler<Self::State>) -> Self;
+
+    /// Add all routes of a controller, created with the [`controller`] macro, to the router.
+    fn typed_controller<C: Controller<State = Self::State>>(self) -> Self;
+
+    /// Add every route in the [`registry`] with a matching state type to the router.
+    #[cfg(feature = "registry")]
+    fn auto_routes(self) -> Self;
 }
 
 impl<S> TypedRouter for axum::Router<S>
@@ -53,7 +139,160 @@ where
     type State = S;
 
     fn typed_route(self, handler: TypedHandler<Self::State>) -> Self {
-        let (path, method_router) = handler();
-        self.route(path, method_router)
+        let (info, method_router) = handler();
+        self.route(info.axum_path, method_router)
+    }
+
+    fn typed_controller<C: Controller<State = Self::State>>(self) -> Self {
+        self.merge(C::into_router())
+    }
+
+    #[cfg(feature = "registry")]
+    fn auto_routes(self) -> Self {
+        registry::register_all(self)
+    }
+}
+
+/// Items used by the code generated by the macros. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    use axum::http::{HeaderMap, StatusCode};
+    pub use axum_extra;
+    pub use axum_macros;
+    #[cfg(feature = "registry")]
+    pub use inventory;
+    use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+    use regex_lite::Regex;
+    pub use serde;
+    use std::{fmt::Display, str::FromStr, sync::OnceLock};
+
+    /// Everything except the unreserved characters of RFC 3986.
+    const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+        .remove(b'-')
+        .remove(b'.')
+        .remove(b'_')
+        .remove(b'~');
+
+    /// Named values of a request that handler arguments are parsed from, such as its headers.
+    pub trait ParamSource {
+        /// What the values are called in error responses, such as `header`.
+        const KIND: &'static str;
+
+        /// The value `name`, or `None` if it's missing.
+        fn value(&self, name: &str) -> Result<Option<&str>, String>;
+    }
+
+    impl ParamSource for HeaderMap {
+        const KIND: &'static str = "header";
+
+        fn value(&self, name: &str) -> Result<Option<&str>, String> {
+            self.get(name)
+                .map(|value| value.to_str().map_err(|err| err.to_string()))
+                .transpose()
+        }
+    }
+
+    #[cfg(feature = "cookies")]
+    impl ParamSource for axum_extra::extract::CookieJar {
+        const KIND: &'static str = "cookie";
+
+        fn value(&self, name: &str) -> Result<Option<&str>, String> {
+            Ok(self.get(name).map(|cookie| cookie.value()))
+        }
+    }
+
+    /// Parses the value `name` of `source`, or returns `None` if it's missing.
+    ///
+    /// Responds with `400 Bad Request` when the value can't be parsed.
+    pub fn optional_param<T, P>(source: &P, name: &str) -> Result<Option<T>, (StatusCode, String)>
+    where
+        T: FromStr,
+        T::Err: Display,
+        P: ParamSource,
+    {
+        let invalid = |err: &dyn Display| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid {} `{name}`: {err}", P::KIND),
+            )
+        };
+        let Some(value) = source.value(name).map_err(|err| invalid(&err))? else {
+            return Ok(None);
+        };
+        value.parse().map(Some).map_err(|err| invalid(&err))
+    }
+
+    /// Parses the value `name` of `source`.
+    ///
+    /// Responds with `400 Bad Request` when the value is missing or can't be parsed.
+    pub fn required_param<T, P>(source: &P, name: &str) -> Result<T, (StatusCode, String)>
+    where
+        T: FromStr,
+        T::Err: Display,
+        P: ParamSource,
+    {
+        optional_param(source, name)?.ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Missing {} `{name}`", P::KIND),
+            )
+        })
+    }
+
+    /// The regex of a path constraint, compiled when it's first used.
+    pub struct PathRegex {
+        pattern: &'static str,
+        regex: OnceLock<Regex>,
+    }
+
+    impl PathRegex {
+        pub const fn new(pattern: &'static str) -> Self {
+            Self {
+                pattern,
+                regex: OnceLock::new(),
+            }
+        }
+
+        pub fn is_match(&self, value: &str) -> bool {
+            self.regex
+                .get_or_init(|| Regex::new(self.pattern).expect("validated by the macro"))
+                .is_match(value)
+        }
+    }
+
+    /// Removes the last segment of `module_path`, such as the companion module of a route.
+    pub const fn parent_module(module_path: &'static str) -> &'static str {
+        let bytes = module_path.as_bytes();
+        let mut i = bytes.len();
+        while i > 1 && !(bytes[i - 1] == b':' && bytes[i - 2] == b':') {
+            i -= 1;
+        }
+        let (parent, _) = bytes.split_at(i.saturating_sub(2));
+        match std::str::from_utf8(parent) {
+            Ok(parent) => parent,
+            Err(_) => panic!("module paths are split at `::`"),
+        }
+    }
+
+    /// Appends a percent-encoded path segment to `url`.
+    pub fn push_path_segment(url: &mut String, value: &impl Display) {
+        url.push('/');
+        url.extend(utf8_percent_encode(&value.to_string(), COMPONENT));
+    }
+
+    /// Appends a percent-encoded wildcard capture to `url`, keeping its slashes.
+    pub fn push_wildcard(url: &mut String, value: &impl Display) {
+        let value = value.to_string();
+        for segment in value.strip_prefix('/').unwrap_or(&value).split('/') {
+            push_path_segment(url, &segment);
+        }
+    }
+
+    /// Appends a percent-encoded query parameter to `url`.
+    pub fn push_query_param(url: &mut String, name: &str, value: &impl Display) {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.extend(utf8_percent_encode(name, COMPONENT));
+        url.push('=');
+        url.extend(utf8_percent_encode(&value.to_string(), COMPONENT));
     }
 }
@@ -0,0 +1,47 @@
+//! OpenAPI documentation of typed routes, built on [`utoipa`] and [`utoipa_axum`].
+//!
+//! Routes created with the [`api_route`](crate::api_route) macro are added to an
+//! [`OpenApiRouter`] with [`TypedApiRouter::typed_api_route`]. The router collects the OpenAPI
+//! documentation of every route, which can be retrieved with [`OpenApiRouter::split_for_parts`].
+
+pub use utoipa;
+pub use utoipa_axum::router::{OpenApiRouter, UtoipaMethodRouter};
+
+/// A typed handler, created with the [`api_route`](crate::api_route) macro.
+pub type TypedApiHandler<S = ()> = fn() -> UtoipaMethodRouter<S>;
+
+/// A trait that allows typed routes, created with the [`api_route`](crate::api_route) macro to
+/// be added to an [`OpenApiRouter`].
+///
+/// Typed api handlers are of the form `fn() -> UtoipaMethodRouter<S>`, where `S` is the state
+/// type. The handler's OpenAPI documentation is added to the router's OpenAPI document.
+pub trait TypedApiRouter: Sized {
+    /// The state type of the router.
+    type State: Clone + Send + Sync + 'static;
+
+    /// Add a typed route to the router, usually created with the [`api_route`](crate::api_route)
+    /// macro.
+    fn typed_api_route(self, handler: TypedApiHandler<Self::State>) -> Self;
+
+    /// Add every route in the [`registry`](crate::registry) with a matching state type to the
+    /// router. Routes created with the [`route`](crate::route) macro are added without OpenAPI
+    /// documentation.
+    #[cfg(feature = "registry")]
+    fn auto_api_routes(self) -> Self;
+}
+
+impl<S> TypedApiRouter for OpenApiRouter<S>
+where
+    S: Send + Sync + Clone + 'static,
+{
+    type State = S;
+
+    fn typed_api_route(self, handler: TypedApiHandler<Self::State>) -> Self {
+        self.routes(handler())
+    }
+
+    #[cfg(feature = "registry")]
+    fn auto_api_routes(self) -> Self {
+        crate::registry::register_all(self)
+    }
+}
@@ -0,0 +1,38 @@
+//! A global registry of typed routes, built on [`inventory`].
+//!
+//! With the `registry` feature, every route created with the [`route`](crate::route) macro, and
+//! every controller created with the [`controller`](crate::controller) macro, registers itself.
+//! [`TypedRouter::auto_routes`](crate::TypedRouter::auto_routes) then adds all registered routes
+//! with a matching state type to a router, so that no route can be forgotten:
+//! ```ignore
+//! let router = axum::Router::new().auto_routes().with_state(state);
+//! ```
+//!
+//! Routes and controllers with generic parameters can't be registered, and have to be added to
+//! the router manually.
+
+use std::any::Any;
+
+/// A route or controller in the registry, which adds itself to a router of type `R`.
+///
+/// Created by the code generated by the macros.
+pub struct RegisteredRoute {
+    register: &'static (dyn Any + Send + Sync),
+}
+
+impl RegisteredRoute {
+    #[doc(hidden)]
+    pub const fn new<R: 'static>(register: &'static fn(R) -> R) -> Self {
+        Self { register }
+    }
+}
+
+inventory::collect!(RegisteredRoute);
+
+/// Adds every registered route, for routers of type `R`, to `router`.
+pub fn register_all<R: 'static>(router: R) -> R {
+    inventory::iter::<RegisteredRoute>
+        .into_iter()
+        .filter_map(|route| route.register.downcast_ref::<fn(R) -> R>())
+        .fold(router, |router, register| register(router))
+}
@@ -0,0 +1,52 @@
+/// A description of a typed route, created by the [`route`](crate::route) and
+/// [`controller`](crate::controller) macros.
+///
+/// Every route has a companion function returning its `RouteInfo`: `info` in the module with
+/// the name of the handler, or `<name>_info` for the routes of a controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// The methods of the route, such as `GET`.
+    pub methods: &'static [&'static str],
+    /// The path as written in the route attribute, including the query.
+    pub path: &'static str,
+    /// The path the route is served at by axum, including the base path of its controller.
+    pub axum_path: &'static str,
+    /// The path captures of the route.
+    pub captures: &'static [ParamInfo],
+    /// The query parameters of the route.
+    pub query_params: &'static [ParamInfo],
+    /// The header parameters of the route.
+    pub headers: &'static [ParamInfo],
+    /// The cookie parameters of the route.
+    pub cookies: &'static [ParamInfo],
+    /// The request body of the route, if it has one.
+    pub body: Option<BodyInfo>,
+    /// The name of the handler, prefixed with the type of its controller if it has one.
+    pub handler: &'static str,
+    /// The path of the module the handler is defined in.
+    pub module_path: &'static str,
+    /// The documentation of the handler.
+    pub docs: &'static str,
+    /// The state type of the route.
+    pub state: &'static str,
+}
+
+/// A path capture, query parameter, header or cookie of a [`RouteInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamInfo {
+    /// The name of the parameter.
+    pub name: &'static str,
+    /// The type of the parameter, as written in the handler.
+    pub ty: &'static str,
+}
+
+/// The request body of a [`RouteInfo`], declared with `body = <TYPE>` in the route attribute, or
+/// inferred from the last argument of the handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyInfo {
+    /// The type of the body extractor, as written in the handler.
+    pub ty: &'static str,
+    /// The content type of the body, if the extractor is well-known, such as `application/json`
+    /// for `Json<T>`.
+    pub content_type: Option<&'static str>,
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use axum::Router;
+
+use crate::{Controller, RouteInfo, TypedHandler, TypedRouter};
+
+/// The routes added to a [`TrackedRouter`], which can be printed as a table with one row per
+/// method and path, sorted by path:
+/// ```text
+/// METHOD  PATH         HANDLER              STATE
+/// GET     /items/{id}  ItemController::get  String
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RouteTable {
+    routes: Vec<&'static RouteInfo>,
+}
+
+impl RouteTable {
+    /// The routes in the order they were added.
+    pub fn routes(&self) -> &[&'static RouteInfo] {
+        &self.routes
+    }
+}
+
+impl fmt::Display for RouteTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = self
+            .routes
+            .iter()
+            .flat_map(|info| {
+                info.methods
+                    .iter()
+                    .map(move |method| [*method, info.axum_path, info.handler, info.state])
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|[method, path, _, _]| (*path, *method));
+        rows.insert(0, ["METHOD", "PATH", "HANDLER", "STATE"]);
+
+        let mut widths = [0; 4];
+        for row in &rows {
+            for (width, column) in widths.iter_mut().zip(row) {
+                *width = (*width).max(column.len());
+            }
+        }
+        for row in &rows {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(column, width)| format!("{column:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// An axum router that keeps a [`RouteTable`] of the typed routes added to it.
+///
+/// Untyped routes, layers, etc. can be added to the wrapped router with
+/// [`TrackedRouter::map_router`], but don't show up in the route table.
+#[derive(Debug, Clone)]
+pub struct TrackedRouter<S = ()> {
+    router: Router<S>,
+    table: RouteTable,
+}
+
+impl<S> TrackedRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    /// Creates an empty router.
+    pub fn new() -> Self {
+        Self {
+            router: Router::new(),
+            table: RouteTable::default(),
+        }
+    }
+
+    /// The routes added to this router.
+    pub fn route_table(&self) -> &RouteTable {
+        &self.table
+    }
+
+    /// Applies `f` to the wrapped router, e.g. to add a layer.
+    pub fn map_router(mut self, f: impl FnOnce(Router<S>) -> Router<S>) -> Self {
+        self.router = f(self.router);
+        self
+    }
+
+    /// The wrapped router.
+    pub fn into_router(self) -> Router<S> {
+        self.router
+    }
+
+    /// The wrapped router, and the routes added to it.
+    pub fn into_parts(self) -> (Router<S>, RouteTable) {
+        (self.router, self.table)
+    }
+}
+
+impl<S> Default for TrackedRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> TypedRouter for TrackedRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    type State = S;
+
+    fn typed_route(mut self, handler: TypedHandler<Self::State>) -> Self {
+        let (info, method_router) = handler();
+        self.router = self.router.route(info.axum_path, method_router);
+        self.table.routes.push(info);
+        self
+    }
+
+    fn typed_controller<C: Controller<State = Self::State>>(mut self) -> Self {
+        self.router = self.router.merge(C::into_router());
+        self.table.routes.extend(C::routes());
+        self
+    }
+
+    #[cfg(feature = "registry")]
+    fn auto_routes(self) -> Self {
+        crate::registry::register_all(self)
+    }
+}
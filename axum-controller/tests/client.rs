@@ -0,0 +1,146 @@
+#![cfg(feature = "client")]
+
+use axum::{extract::State, Json};
+use axum_controller::{client::Client, controller, route, TypedRouter};
+
+#[route(GET "/item/{id}?amount&offset", client)]
+async fn item_handler(id: u32, amount: Option<u32>, offset: Option<u32>) -> String {
+    format!("item {id} - {amount:?} - {offset:?}")
+}
+
+#[route(POST | PUT "/echo/{*rest}", client)]
+async fn echo(rest: String, body: String) -> String {
+    format!("{rest}: {body}")
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Item {
+    name: String,
+}
+
+#[route(PUT "/items/{id}", client)]
+async fn put_item(id: u32, Json(item): Json<Item>) -> String {
+    format!("{id}: {}", item.name)
+}
+
+/// Only deserialized by the server, so routes without the `client` option must still compile.
+#[derive(serde::Deserialize)]
+struct NewItem {
+    name: String,
+}
+
+#[route(POST "/items/{id}", url)]
+async fn post_item(id: u32, Json(item): Json<NewItem>) -> String {
+    format!("{id}: {}", item.name)
+}
+
+#[route(GET "/whoami", headers(request_id = "X-Request-Id", if_match), client)]
+async fn whoami(request_id: u32, if_match: Option<String>) -> String {
+    format!("{request_id} - {if_match:?}")
+}
+
+#[cfg(feature = "cookies")]
+#[route(GET "/preferences", cookies(session, theme), client)]
+async fn preferences(session: String, theme: Option<String>) -> String {
+    format!("{session} - {theme:?}")
+}
+
+#[route(PROPFIND "/dav", client)]
+async fn dav() -> &'static str {
+    "dav"
+}
+
+struct UsersController;
+
+#[controller(path = "/users", state = String)]
+impl UsersController {
+    #[route(GET "/{name}", client)]
+    async fn get(State(state): State<String>, name: String) -> String {
+        format!("{state}: {name}")
+    }
+}
+
+async fn serve(router: axum::Router) -> Client {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    Client::new(format!("http://{address}/"))
+}
+
+#[tokio::test]
+async fn test_client() {
+    let router = axum::Router::new()
+        .typed_route(item_handler)
+        .typed_route(echo)
+        .typed_route(put_item)
+        .typed_route(post_item)
+        .typed_route(whoami)
+        .typed_route(dav)
+        .merge(UsersController::into_router().with_state("users".to_string()));
+    let client = serve(router).await;
+
+    let response = item_handler::request(&client, 1, Some(2), None)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "item 1 - Some(2) - None");
+
+    let response = echo::request_post(&client, "a b/c".to_string(), "hello".to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "a b/c: hello");
+
+    let response = echo::request_put(&client, "d".to_string(), "bye".to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "d: bye");
+
+    let item = Item {
+        name: "Box".to_string(),
+    };
+    let response = put_item::request(&client, 1, &item).send().await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "1: Box");
+
+    assert_eq!(post_item::url(1), "/items/1");
+
+    let response = whoami::request(&client, 7, None).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "7 - None");
+
+    let response = whoami::request(&client, 7, Some("abc".to_string()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), r#"7 - Some("abc")"#);
+
+    let response = dav::request(&client).send().await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "dav");
+
+    let response = UsersController::get_request(&client, "John".to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "users: John");
+}
+
+#[cfg(feature = "cookies")]
+#[tokio::test]
+async fn test_client_cookies() {
+    let client = serve(axum::Router::new().typed_route(preferences)).await;
+
+    let response = preferences::request(&client, "abc".to_string(), None)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "abc - None");
+
+    let response = preferences::request(&client, "abc".to_string(), Some("dark".to_string()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), r#"abc - Some("dark")"#);
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "cookies")]
+
+use axum_controller::{route, ParamInfo, TypedRouter};
+use axum_test::TestServer;
+
+#[route(GET "/profile", cookies(session_id, theme, locale = "lang"))]
+async fn profile(session_id: u64, theme: Option<String>, locale: Option<String>) -> String {
+    format!("{session_id} - {theme:?} - {locale:?}")
+}
+
+#[tokio::test]
+async fn test_cookies() {
+    let router: axum::Router = axum::Router::new().typed_route(profile);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server
+        .get("/profile")
+        .add_header("cookie", "session_id=42; theme=dark; lang=nl")
+        .await;
+    response.assert_status_ok();
+    response.assert_text(r#"42 - Some("dark") - Some("nl")"#);
+
+    let response = server
+        .get("/profile")
+        .add_header("cookie", "session_id=42")
+        .await;
+    response.assert_status_ok();
+    response.assert_text("42 - None - None");
+
+    let response = server.get("/profile").await;
+    response.assert_status_bad_request();
+
+    let response = server
+        .get("/profile")
+        .add_header("cookie", "session_id=abc")
+        .await;
+    response.assert_status_bad_request();
+
+    assert_eq!(
+        profile::info().cookies,
+        &[
+            ParamInfo {
+                name: "session_id",
+                ty: "u64",
+            },
+            ParamInfo {
+                name: "theme",
+                ty: "Option<String>",
+            },
+            ParamInfo {
+                name: "lang",
+                ty: "Option<String>",
+            },
+        ]
+    );
+}
@@ -1,5 +1,7 @@
 #![allow(unused)]
 #![allow(clippy::extra_unused_type_parameters)]
+// The legacy path syntax is tested on purpose
+#![cfg_attr(feature = "legacy-path-warnings", allow(deprecated))]
 
 use std::net::TcpListener;
 
@@ -8,9 +10,9 @@ use axum::{
     routing::get,
     Form, Json,
 };
+use axum_controller::{BodyInfo, ParamInfo, TrackedRouter, TypedRouter};
+use axum_controller_macros::{controller, route};
 use axum_test::TestServer;
-use axum_controller::TypedRouter;
-use axum_controller_macros::route;
 
 /// This is a handler that is documented!
 #[route(GET "/hello/:id?user_id&name")]
@@ -87,8 +89,8 @@ async fn test_normal() {
     response.assert_status_ok();
     response.assert_text("Hello, 123 - 321 - John!");
 
-    let (path, method_router) = generic_handler_with_complex_options::<u32>();
-    assert_eq!(path, "/hello/{id}");
+    let (info, method_router) = generic_handler_with_complex_options::<u32>();
+    assert_eq!(info.axum_path, "/hello/{id}");
 }
 
 #[route(GET "/*")]
@@ -112,3 +114,819 @@ async fn test_wildcard() {
     response.assert_status_ok();
     assert_eq!(response.json::<String>(), "foo/bar");
 }
+
+struct UsersController;
+
+async fn add_controller_header(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .insert("x-controller", "users".parse().unwrap());
+    response
+}
+
+#[controller(
+    path = "/users",
+    state = String,
+    middleware = axum::middleware::from_fn(add_controller_header)
+)]
+impl UsersController {
+    #[route(GET "/", url)]
+    async fn list(State(state): State<String>) -> String {
+        format!("users of {state}")
+    }
+
+    #[route(GET "/:id?name", url)]
+    async fn get(id: u32, name: Option<String>) -> String {
+        format!("user {id} - {name:?}")
+    }
+
+    fn helper() -> u32 {
+        5
+    }
+}
+
+struct LayeredController;
+
+async fn append_order(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+    name: &'static str,
+) -> axum::response::Response {
+    let mut response = next.run(request).await;
+    let order = match response.headers().get("x-order") {
+        Some(order) => format!("{},{name}", order.to_str().unwrap()),
+        None => name.to_string(),
+    };
+    response
+        .headers_mut()
+        .insert("x-order", order.parse().unwrap());
+    response
+}
+
+#[controller(
+    path = "/layered",
+    middleware = axum::middleware::from_fn(|request, next| append_order(request, next, "a")),
+    route_middleware = axum::middleware::from_fn(|request, next| append_order(request, next, "b")),
+    middleware = axum::middleware::from_fn(|request, next| append_order(request, next, "c"))
+)]
+impl LayeredController {
+    #[route(GET "/")]
+    async fn index() -> &'static str {
+        "layered"
+    }
+}
+
+#[tokio::test]
+async fn test_controller_layer_order() {
+    let router: axum::Router = LayeredController::into_router();
+
+    let server = TestServer::new(router).unwrap();
+
+    // Every layer wraps the layers declared before it
+    let response = server.get("/layered").await;
+    response.assert_text("layered");
+    response.assert_header("x-order", "a,b,c");
+
+    // Route layers are not applied to the fallback
+    let response = server.get("/layered/missing").await;
+    response.assert_status_not_found();
+    response.assert_header("x-order", "a,c");
+}
+
+#[tokio::test]
+async fn test_controller() {
+    let router: axum::Router = UsersController::into_router().with_state("state".to_string());
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/users").await;
+    response.assert_status_ok();
+    response.assert_text("users of state");
+    response.assert_header("x-controller", "users");
+
+    let response = server
+        .get("/users/123")
+        .add_query_param("name", "John")
+        .await;
+    response.assert_status_ok();
+    response.assert_text("user 123 - Some(\"John\")");
+
+    assert_eq!(UsersController::helper(), 5);
+}
+
+#[route(PATCH "/patch/:id")]
+async fn patch(id: u32) -> String {
+    format!("Patched {id}!")
+}
+
+#[route(PROPFIND "/dav/*path")]
+async fn propfind(path: String) -> String {
+    format!("Found {path}!")
+}
+
+#[route("READ" | "M-SEARCH" "/literal/{id}")]
+async fn literal_methods(id: u32) -> String {
+    format!("Literal {id}!")
+}
+
+#[tokio::test]
+async fn test_methods() {
+    let router: axum::Router = axum::Router::new()
+        .typed_route(patch)
+        .typed_route(propfind)
+        .typed_route(literal_methods);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.patch("/patch/1").await;
+    response.assert_status_ok();
+    response.assert_text("Patched 1!");
+
+    let propfind = axum::http::Method::from_bytes(b"PROPFIND").unwrap();
+    let response = server.method(propfind, "/dav/foo/bar").await;
+    response.assert_status_ok();
+    response.assert_text("Found foo/bar!");
+
+    let response = server.get("/dav/foo/bar").await;
+    response.assert_status(axum::http::StatusCode::METHOD_NOT_ALLOWED);
+    response.assert_header("allow", "PROPFIND");
+
+    for method in ["READ", "M-SEARCH"] {
+        let method = axum::http::Method::from_bytes(method.as_bytes()).unwrap();
+        let response = server.method(method, "/literal/1").await;
+        response.assert_status_ok();
+        response.assert_text("Literal 1!");
+    }
+    assert_eq!(literal_methods::info().methods, ["READ", "M-SEARCH"]);
+}
+
+#[route(PUT | PATCH "/multi/:id")]
+async fn put_or_patch(id: u32) -> String {
+    format!("Updated {id}!")
+}
+
+#[route(GET | MKCOL "/collection")]
+async fn get_or_mkcol() -> String {
+    String::from("Collection!")
+}
+
+#[tokio::test]
+async fn test_multiple_methods() {
+    let router: axum::Router = axum::Router::new()
+        .typed_route(put_or_patch)
+        .typed_route(get_or_mkcol);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.put("/multi/1").await;
+    response.assert_status_ok();
+    response.assert_text("Updated 1!");
+
+    let response = server.patch("/multi/2").await;
+    response.assert_status_ok();
+    response.assert_text("Updated 2!");
+
+    let response = server.post("/multi/3").await;
+    response.assert_status(axum::http::StatusCode::METHOD_NOT_ALLOWED);
+
+    let mkcol = axum::http::Method::from_bytes(b"MKCOL").unwrap();
+    let response = server.method(mkcol, "/collection").await;
+    response.assert_status_ok();
+    response.assert_text("Collection!");
+
+    let response = server.get("/collection").await;
+    response.assert_status_ok();
+
+    let response = server.method(axum::http::Method::HEAD, "/collection").await;
+    response.assert_status_ok();
+
+    let response = server.delete("/collection").await;
+    response.assert_status(axum::http::StatusCode::METHOD_NOT_ALLOWED);
+    response.assert_header("allow", "GET,MKCOL,HEAD");
+}
+
+#[route(GET "/braces/{id}/{*rest}", url)]
+async fn braces(id: u32, rest: String) -> String {
+    format!("{id} - {rest}")
+}
+
+#[tokio::test]
+async fn test_brace_syntax() {
+    let router: axum::Router = axum::Router::new().typed_route(braces);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/braces/1/foo/bar").await;
+    response.assert_status_ok();
+    response.assert_text("1 - foo/bar");
+
+    let (info, _) = braces();
+    assert_eq!(info.axum_path, "/braces/{id}/{*rest}");
+}
+
+#[derive(Clone)]
+struct AppState {
+    greeting: String,
+}
+
+#[derive(Clone)]
+struct GreetController {
+    greeting: String,
+}
+
+impl axum::extract::FromRef<AppState> for GreetController {
+    fn from_ref(state: &AppState) -> Self {
+        Self {
+            greeting: state.greeting.clone(),
+        }
+    }
+}
+
+impl axum::extract::FromRef<AppState> for std::sync::Arc<GreetController> {
+    fn from_ref(state: &AppState) -> Self {
+        std::sync::Arc::new(GreetController::from_ref(state))
+    }
+}
+
+#[controller(path = "/greet", state = AppState)]
+impl GreetController {
+    #[route(GET "/{name}?punctuation", url)]
+    async fn greet(
+        &self,
+        State(state): State<AppState>,
+        name: String,
+        punctuation: char,
+    ) -> String {
+        format!("{}, {name}{punctuation}", self.greeting)
+    }
+
+    #[route(GET "/arc/{name}")]
+    async fn greet_arc(self: std::sync::Arc<Self>, name: String) -> Json<Self> {
+        Json(Self {
+            greeting: format!("{}, {name}!", self.greeting),
+        })
+    }
+}
+
+impl serde::Serialize for GreetController {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.greeting)
+    }
+}
+
+#[tokio::test]
+async fn test_controller_receiver() {
+    let router: axum::Router = GreetController::into_router().with_state(AppState {
+        greeting: "Hello".to_string(),
+    });
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server
+        .get("/greet/John")
+        .add_query_param("punctuation", '?')
+        .await;
+    response.assert_status_ok();
+    response.assert_text("Hello, John?");
+
+    let response = server.get("/greet/arc/John").await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<String>(), "Hello, John!");
+}
+
+#[route(GET "/url/:id", url)]
+async fn url_capture(id: u32) {}
+
+#[route(GET "/url-query?id", url)]
+async fn url_query(id: u32) {}
+
+#[route(GET "/url-static", url)]
+async fn url_static() {}
+
+#[route(GET "/url-generic/:id?user_id&name", url)]
+async fn url_generic_query<T: 'static>(id: u32, user_id: String, name: String) {}
+
+#[test]
+fn test_url() {
+    assert_eq!(url_capture::url(123), "/url/123");
+    assert_eq!(url_query::url(7), "/url-query?id=7");
+    assert_eq!(url_static::url(), "/url-static");
+    assert_eq!(
+        url_generic_query::url(1, "a b".to_string(), "ü/?".to_string()),
+        "/url-generic/1?user_id=a%20b&name=%C3%BC%2F%3F"
+    );
+    assert_eq!(
+        braces::url(1, "foo bar/baz".to_string()),
+        "/braces/1/foo%20bar/baz"
+    );
+
+    assert_eq!(UsersController::list_url(), "/users");
+    assert_eq!(UsersController::get_url(5, None), "/users/5");
+    assert_eq!(
+        UsersController::get_url(5, Some("John Doe".to_string())),
+        "/users/5?name=John%20Doe"
+    );
+    assert_eq!(
+        GreetController::greet_url("John".to_string(), '!'),
+        "/greet/John?punctuation=%21"
+    );
+}
+
+struct PostsController;
+
+#[controller(path = "/posts")]
+impl PostsController {
+    #[route(GET "/{id}")]
+    async fn get(id: u32) -> String {
+        format!("get {id}")
+    }
+
+    #[route(PUT | DELETE "/{id}")]
+    async fn modify(id: u32) -> String {
+        format!("modify {id}")
+    }
+
+    #[route(HEAD "/{id}")]
+    async fn head(id: u32) -> [(&'static str, String); 1] {
+        [("x-post-id", id.to_string())]
+    }
+
+    #[route(GET "/latest")]
+    async fn latest() -> &'static str {
+        "latest"
+    }
+}
+
+#[tokio::test]
+async fn test_controller_shared_paths() {
+    let router: axum::Router = PostsController::into_router();
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/posts/1").await;
+    response.assert_text("get 1");
+
+    let response = server.delete("/posts/1").await;
+    response.assert_text("modify 1");
+
+    let response = server.method(axum::http::Method::HEAD, "/posts/1").await;
+    response.assert_header("x-post-id", "1");
+
+    let response = server.get("/posts/latest").await;
+    response.assert_text("latest");
+}
+
+#[test]
+fn test_route_info() {
+    let info = generic_handler_with_complex_options::info();
+    assert_eq!(info.methods, ["GET"]);
+    assert_eq!(info.path, "/hello/:id?user_id&name");
+    assert_eq!(info.axum_path, "/hello/{id}");
+    assert_eq!(
+        info.captures,
+        [ParamInfo {
+            name: "id",
+            ty: "u32"
+        }]
+    );
+    assert_eq!(
+        info.query_params,
+        [
+            ParamInfo {
+                name: "user_id",
+                ty: "String"
+            },
+            ParamInfo {
+                name: "name",
+                ty: "String"
+            }
+        ]
+    );
+    assert_eq!(info.handler, "generic_handler_with_complex_options");
+    assert_eq!(info.module_path, "main");
+    assert_eq!(info.docs, "This is a handler that is documented!");
+    assert_eq!(info.state, "String");
+
+    let info = put_or_patch::info();
+    assert_eq!(info.methods, ["PUT", "PATCH"]);
+
+    let info = UsersController::get_info();
+    assert_eq!(info.axum_path, "/users/{id}");
+    assert_eq!(info.handler, "UsersController::get");
+    assert_eq!(
+        info.query_params,
+        [ParamInfo {
+            name: "name",
+            ty: "Option<String>"
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_route_table() {
+    let router = TrackedRouter::new()
+        .typed_route(three)
+        .typed_route(put_or_patch)
+        .typed_controller::<PostsController>();
+
+    assert_eq!(
+        router.route_table().to_string(),
+        "\
+METHOD  PATH           HANDLER                  STATE
+PATCH   /multi/{id}    put_or_patch             ()
+PUT     /multi/{id}    put_or_patch             ()
+GET     /posts/latest  PostsController::latest  ()
+DELETE  /posts/{id}    PostsController::modify  ()
+GET     /posts/{id}    PostsController::get     ()
+HEAD    /posts/{id}    PostsController::head    ()
+PUT     /posts/{id}    PostsController::modify  ()
+GET     /three/{id}    three                    ()
+"
+    );
+
+    let server = TestServer::new(router.into_router()).unwrap();
+
+    let response = server.get("/posts/latest").await;
+    response.assert_text("latest");
+
+    let response = server.get("/three/1").await;
+    response.assert_text("Hello 1!");
+}
+
+#[route(GET r#"/search?page_size as "page-size" = 20&sort as "sort[by]"&query = "all".to_string()"#, url)]
+async fn search(page_size: u32, sort: Option<String>, query: String) -> String {
+    format!("{query} - {page_size} - {sort:?}")
+}
+
+#[tokio::test]
+async fn test_query_rename_and_default() {
+    let router: axum::Router = axum::Router::new().typed_route(search);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/search").await;
+    response.assert_status_ok();
+    response.assert_text("all - 20 - None");
+
+    let response = server
+        .get("/search")
+        .add_query_param("page-size", 5)
+        .add_query_param("sort[by]", "name")
+        .add_query_param("query", "foo")
+        .await;
+    response.assert_status_ok();
+    response.assert_text("foo - 5 - Some(\"name\")");
+
+    assert_eq!(
+        search::url(5, Some("name".to_string()), "foo".to_string()),
+        "/search?page-size=5&sort%5Bby%5D=name&query=foo"
+    );
+    assert_eq!(search::info().query_params[0].name, "page-size");
+}
+
+#[route(GET "/filter?tag&id&limit", url)]
+async fn filter(
+    tag: Vec<String>,
+    id: std::collections::BTreeSet<u32>,
+    limit: Option<u32>,
+) -> String {
+    format!("{tag:?} - {id:?} - {limit:?}")
+}
+
+#[route(GET "/optional-filter?tag", url)]
+async fn optional_filter(tag: Option<Vec<String>>) -> String {
+    format!("{tag:?}")
+}
+
+#[tokio::test]
+async fn test_query_collections() {
+    let router: axum::Router = axum::Router::new()
+        .typed_route(filter)
+        .typed_route(optional_filter);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/filter?tag=a&tag=b&id=2&id=1&limit=3").await;
+    response.assert_status_ok();
+    response.assert_text(r#"["a", "b"] - {1, 2} - Some(3)"#);
+
+    let response = server.get("/filter").await;
+    response.assert_status_ok();
+    response.assert_text("[] - {} - None");
+
+    assert_eq!(
+        filter::url(vec!["a".to_string(), "b".to_string()], [1].into(), None),
+        "/filter?tag=a&tag=b&id=1"
+    );
+
+    let response = server.get("/optional-filter?tag=a&tag=b").await;
+    response.assert_status_ok();
+    response.assert_text(r#"Some(["a", "b"])"#);
+
+    let response = server.get("/optional-filter").await;
+    response.assert_status_ok();
+    response.assert_text("None");
+
+    assert_eq!(
+        optional_filter::url(Some(vec!["a".to_string(), "b".to_string()])),
+        "/optional-filter?tag=a&tag=b"
+    );
+    assert_eq!(optional_filter::url(None), "/optional-filter");
+}
+
+#[route(GET "/headers", headers(request_id = "X-Request-Id", if_match))]
+async fn headers(request_id: u32, if_match: Option<String>) -> String {
+    format!("{request_id} - {if_match:?}")
+}
+
+#[tokio::test]
+async fn test_headers() {
+    let router: axum::Router = axum::Router::new().typed_route(headers);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server
+        .get("/headers")
+        .add_header("x-request-id", "7")
+        .add_header("if-match", "abc")
+        .await;
+    response.assert_status_ok();
+    response.assert_text(r#"7 - Some("abc")"#);
+
+    let response = server.get("/headers").add_header("x-request-id", "7").await;
+    response.assert_status_ok();
+    response.assert_text("7 - None");
+
+    let response = server.get("/headers").await;
+    response.assert_status_bad_request();
+
+    let response = server
+        .get("/headers")
+        .add_header("x-request-id", "seven")
+        .await;
+    response.assert_status_bad_request();
+
+    assert_eq!(
+        headers::info().headers,
+        &[
+            ParamInfo {
+                name: "X-Request-Id",
+                ty: "u32",
+            },
+            ParamInfo {
+                name: "if-match",
+                ty: "Option<String>",
+            },
+        ]
+    );
+    assert!(headers::info().query_params.is_empty());
+}
+
+#[route(POST "/body/{id}")]
+async fn inferred_body(Json(amount): Json<u32>, id: u32) -> String {
+    format!("{id}: {amount}")
+}
+
+#[route(PUT "/body", body = Form<Vec<(String, String)>>)]
+async fn declared_body(State(_): State<()>, Form(fields): Form<Vec<(String, String)>>) -> String {
+    format!("{fields:?}")
+}
+
+#[tokio::test]
+async fn test_request_body() {
+    let router: axum::Router = axum::Router::new()
+        .typed_route(inferred_body)
+        .typed_route(declared_body);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.post("/body/1").json(&2).await;
+    response.assert_status_ok();
+    response.assert_text("1: 2");
+
+    let response = server.put("/body").form(&[("a", "b")]).await;
+    response.assert_status_ok();
+    response.assert_text(r#"[("a", "b")]"#);
+
+    assert_eq!(
+        inferred_body::info().body,
+        Some(BodyInfo {
+            ty: "Json<u32>",
+            content_type: Some("application/json"),
+        })
+    );
+    assert_eq!(
+        declared_body::info().body,
+        Some(BodyInfo {
+            ty: "Form<Vec<(String, String)>>",
+            content_type: Some("application/x-www-form-urlencoded"),
+        })
+    );
+    assert_eq!(three::info().body, None);
+}
+
+/// Documented.
+#[doc(hidden)]
+#[doc = concat!("Not", " a literal.")]
+#[route(GET "/hidden")]
+async fn hidden() {}
+
+#[test]
+fn test_non_literal_docs() {
+    assert_eq!(hidden::info().docs, "Documented.");
+}
+
+#[derive(serde::Deserialize)]
+struct UserId(u32);
+
+#[derive(serde::Deserialize)]
+struct Page(u32);
+
+#[route(GET "/users/{id}/posts?page&sort")]
+async fn user_posts(
+    #[path] UserId(id): UserId,
+    #[query("page")] Page(p): Page,
+    sort: Option<String>,
+) -> String {
+    format!("{id} - {p} - {sort:?}")
+}
+
+#[tokio::test]
+async fn test_destructured_params() {
+    let router: axum::Router = axum::Router::new().typed_route(user_posts);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/users/1/posts?page=2&sort=new").await;
+    response.assert_status_ok();
+    response.assert_text(r#"1 - 2 - Some("new")"#);
+
+    let response = server.get("/users/1/posts").await;
+    response.assert_status_bad_request();
+
+    assert_eq!(
+        user_posts::info().captures,
+        &[ParamInfo {
+            name: "id",
+            ty: "UserId",
+        }]
+    );
+}
+
+#[route(POST "/declared/{id}")]
+async fn declared(
+    #[path] id: u32,
+    #[query] Page(page): Page,
+    #[header("X-Request-Id")] request_id: Option<u32>,
+    #[body] axum::Json(value): axum::Json<u32>,
+) -> String {
+    format!("{id} - {page} - {request_id:?} - {value}")
+}
+
+#[tokio::test]
+async fn test_declared_params() {
+    let router: axum::Router = axum::Router::new().typed_route(declared);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server
+        .post("/declared/1?page=2")
+        .add_header("X-Request-Id", "3")
+        .json(&4)
+        .await;
+    response.assert_status_ok();
+    response.assert_text("1 - 2 - Some(3) - 4");
+
+    let response = server.post("/declared/1").json(&4).await;
+    response.assert_status_bad_request();
+
+    assert_eq!(
+        declared::info().query_params,
+        &[ParamInfo {
+            name: "page",
+            ty: "Page",
+        }]
+    );
+    assert_eq!(
+        declared::info().headers,
+        &[ParamInfo {
+            name: "X-Request-Id",
+            ty: "Option<u32>",
+        }]
+    );
+    assert_eq!(
+        declared::info().body,
+        Some(BodyInfo {
+            ty: "axum::Json<u32>",
+            content_type: Some("application/json"),
+        })
+    );
+}
+
+#[route(GET "/constrained/{id<u32>}/{page<1..=100>}/{slug<[a-z-]+>}", url)]
+async fn constrained(id: u32, page: u32, slug: String) -> String {
+    format!("{id} - {page} - {slug}")
+}
+
+#[route(GET r"/digits/:id<\d{1,3}>")]
+async fn digits(id: String) -> String {
+    id
+}
+
+#[tokio::test]
+async fn test_path_constraints() {
+    let router: axum::Router = axum::Router::new()
+        .typed_route(constrained)
+        .typed_route(digits);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/constrained/1/100/hello-world").await;
+    response.assert_status_ok();
+    response.assert_text("1 - 100 - hello-world");
+
+    let response = server.get("/constrained/a/1/hello").await;
+    response.assert_status_not_found();
+
+    let response = server.get("/constrained/1/101/hello").await;
+    response.assert_status_not_found();
+
+    let response = server.get("/constrained/1/1/Hello").await;
+    response.assert_status_not_found();
+
+    let response = server.get("/digits/123").await;
+    response.assert_status_ok();
+    response.assert_text("123");
+
+    let response = server.get("/digits/1234").await;
+    response.assert_status_not_found();
+
+    assert_eq!(
+        constrained::info().axum_path,
+        "/constrained/{id}/{page}/{slug}"
+    );
+    assert_eq!(digits::info().axum_path, "/digits/{id}");
+    assert_eq!(
+        constrained::url(1, 2, "a".to_string()),
+        "/constrained/1/2/a"
+    );
+}
+
+struct GenericController<T>(std::marker::PhantomData<T>);
+
+#[controller(path = "/generic")]
+impl<T: std::fmt::Display + Default + Send + Sync + 'static> GenericController<T> {
+    #[route(GET "/{id}")]
+    async fn get(id: u32) -> String {
+        format!("{id} - {}", T::default())
+    }
+}
+
+#[tokio::test]
+async fn test_generic_controller() {
+    let router: axum::Router = GenericController::<u8>::into_router();
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/generic/1").await;
+    response.assert_status_ok();
+    response.assert_text("1 - 0");
+}
+
+/// A path parameter that can't be displayed, which is fine for routes without `url`.
+#[derive(serde::Deserialize)]
+struct Slug(String);
+
+#[route(GET "/slugs/{slug}?tags")]
+async fn slug_tags(#[path] Slug(slug): Slug, tags: Option<Vec<String>>) -> String {
+    format!("{slug} - {tags:?}")
+}
+
+#[route(GET "/generic-url/{id}", url)]
+async fn generic_url<T>(id: T) -> String
+where
+    T: serde::de::DeserializeOwned + std::fmt::Display + Send + 'static,
+{
+    id.to_string()
+}
+
+#[tokio::test]
+async fn test_url_opt_in() {
+    let router: axum::Router = axum::Router::new()
+        .typed_route(slug_tags)
+        .typed_route(generic_url::<u32>);
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/slugs/a").await;
+    response.assert_text("a - None");
+
+    let response = server.get("/generic-url/5").await;
+    response.assert_text("5");
+
+    assert_eq!(generic_url::url(5), "/generic-url/5");
+}
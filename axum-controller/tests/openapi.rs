@@ -0,0 +1,184 @@
+#![cfg(feature = "openapi")]
+#![allow(unused)]
+
+use axum::extract::State;
+use axum_controller::{
+    api_route, controller,
+    openapi::{utoipa::openapi::HttpMethod, OpenApiRouter, TypedApiRouter},
+};
+use axum_test::TestServer;
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+struct Item {
+    id: u32,
+    name: String,
+}
+
+/// Get an item.
+///
+/// Returns the item with the given id.
+#[api_route(
+    GET "/item/{id}?amount",
+    responses = { 200: Item, 404: String },
+    security = { "api_key": ["read"] },
+    tags = ["items"],
+)]
+async fn get_item(id: u32, amount: Option<u32>) -> axum::Json<Item> {
+    axum::Json(Item {
+        id,
+        name: format!("{amount:?}"),
+    })
+}
+
+#[api_route(POST | PUT "/item")]
+async fn upsert_item() {}
+
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+struct NewItem {
+    name: String,
+}
+
+#[api_route(POST "/items", body = axum::Json<NewItem>)]
+async fn create_item(axum::Json(item): axum::Json<NewItem>) -> String {
+    item.name
+}
+
+#[api_route(PUT "/item/{id}/name")]
+async fn rename_item(id: u32, name: String) -> String {
+    format!("{id}: {name}")
+}
+
+#[tokio::test]
+async fn test_api_route() {
+    let (router, openapi) = OpenApiRouter::new()
+        .typed_api_route(get_item)
+        .typed_api_route(upsert_item)
+        .split_for_parts();
+
+    let server = TestServer::new(router).unwrap();
+    let response = server.get("/item/1").add_query_param("amount", 2).await;
+    response.assert_status_ok();
+
+    let item = openapi.paths.get_path_item("/item/{id}").unwrap();
+    let operation = item.get.as_ref().unwrap();
+    assert_eq!(operation.operation_id.as_deref(), Some("get_item"));
+    assert_eq!(operation.summary.as_deref(), Some("Get an item."));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Returns the item with the given id.")
+    );
+    assert_eq!(operation.tags, Some(vec!["items".to_string()]));
+
+    let parameters = operation.parameters.as_ref().unwrap();
+    assert_eq!(parameters.len(), 2);
+    assert_eq!(parameters[0].name, "id");
+    assert_eq!(parameters[1].name, "amount");
+
+    assert!(operation.responses.responses.contains_key("200"));
+    assert!(operation.responses.responses.contains_key("404"));
+    assert_eq!(operation.security.as_ref().unwrap().len(), 1);
+
+    let json = openapi.to_json().unwrap();
+    assert!(json.contains("\"api_key\":[\"read\"]"));
+
+    let item = openapi.paths.get_path_item("/item").unwrap();
+    assert!(item.post.is_some());
+    assert!(item.put.is_some());
+    assert!(item.get.is_none());
+}
+
+struct ItemController;
+
+/// An id without a schema, which can only be used in undocumented routes.
+#[derive(serde::Deserialize)]
+struct ItemId(u32);
+
+#[controller(path = "/items", state = String)]
+impl ItemController {
+    /// List all items.
+    #[api_route(GET "/", responses = { 200: Vec<Item> })]
+    async fn list(State(state): State<String>) -> String {
+        state
+    }
+
+    #[route(GET "/{id}", tags = ["items"])]
+    async fn get(id: u32) -> String {
+        format!("item {id}")
+    }
+
+    #[route(DELETE "/{id}")]
+    async fn delete(#[path] ItemId(id): ItemId) -> String {
+        format!("deleted {id}")
+    }
+
+    #[route(PROPFIND "/dav", tags = ["items"])]
+    async fn dav() -> &'static str {
+        "dav"
+    }
+}
+
+#[tokio::test]
+async fn test_controller_openapi() {
+    let (router, openapi) = ItemController::into_openapi_router().split_for_parts();
+    let router = router.with_state("state".to_string());
+
+    let server = TestServer::new(router).unwrap();
+    let response = server.get("/items").await;
+    response.assert_status_ok();
+    response.assert_text("state");
+
+    let operation = openapi.paths.get_path_operation("/items", HttpMethod::Get);
+    assert_eq!(
+        operation.unwrap().summary.as_deref(),
+        Some("List all items.")
+    );
+
+    let response = server.delete("/items/1").await;
+    response.assert_text("deleted 1");
+
+    // Only routes with OpenAPI options are documented
+    let item = openapi.paths.get_path_item("/items/{id}").unwrap();
+    assert_eq!(
+        item.get.as_ref().unwrap().operation_id.as_deref(),
+        Some("get")
+    );
+    assert!(item.delete.is_none());
+
+    // Custom methods are served, but not documented
+    let propfind = axum::http::Method::from_bytes(b"PROPFIND").unwrap();
+    let response = server.method(propfind, "/items/dav").await;
+    response.assert_text("dav");
+    assert!(openapi.paths.get_path_item("/items/dav").is_none());
+}
+
+#[tokio::test]
+async fn test_api_route_body() {
+    let (router, openapi) = OpenApiRouter::new()
+        .typed_api_route(create_item)
+        .typed_api_route(rename_item)
+        .split_for_parts();
+
+    let server = TestServer::new(router).unwrap();
+    let response = server
+        .post("/items")
+        .json(&NewItem {
+            name: "item".to_string(),
+        })
+        .await;
+    response.assert_status_ok();
+    response.assert_text("item");
+
+    let operation = openapi
+        .paths
+        .get_path_operation("/items", HttpMethod::Post)
+        .unwrap();
+    let body = operation.request_body.as_ref().unwrap();
+    assert!(body.content["application/json"].schema.is_some());
+
+    let operation = openapi
+        .paths
+        .get_path_operation("/item/{id}/name", HttpMethod::Put)
+        .unwrap();
+    let body = operation.request_body.as_ref().unwrap();
+    assert!(body.content["text/plain"].schema.is_none());
+}
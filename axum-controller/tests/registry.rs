@@ -0,0 +1,119 @@
+#![cfg(feature = "registry")]
+
+use axum::extract::State;
+use axum_controller::{controller, route, TrackedRouter, TypedRouter};
+use axum_test::TestServer;
+
+#[route(GET "/hello/{name}")]
+async fn hello(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+#[route(GET "/state")]
+async fn state(State(state): State<String>) -> String {
+    state
+}
+
+// Generic routes are not registered
+#[route(GET "/generic")]
+async fn generic<T: Default + std::fmt::Debug + 'static>() -> String {
+    format!("{:?}", T::default())
+}
+
+struct ItemsController;
+
+#[controller(path = "/items")]
+impl ItemsController {
+    #[route(GET "/{id}")]
+    async fn get(id: u32) -> String {
+        format!("item {id}")
+    }
+}
+
+#[tokio::test]
+async fn test_auto_routes() {
+    let router: axum::Router = axum::Router::new().auto_routes();
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/hello/John").await;
+    response.assert_status_ok();
+    response.assert_text("Hello, John!");
+
+    let response = server.get("/items/1").await;
+    response.assert_status_ok();
+    response.assert_text("item 1");
+
+    // Registered for a different state type
+    let response = server.get("/state").await;
+    response.assert_status_not_found();
+
+    let response = server.get("/generic").await;
+    response.assert_status_not_found();
+}
+
+#[test]
+fn test_auto_routes_route_table() {
+    let router = TrackedRouter::<()>::new().auto_routes();
+
+    let handlers = router
+        .route_table()
+        .routes()
+        .iter()
+        .map(|info| info.handler)
+        .collect::<Vec<_>>();
+    assert!(handlers.contains(&"hello"));
+    assert!(handlers.contains(&"ItemsController::get"));
+    assert!(!handlers.contains(&"state"));
+}
+
+#[tokio::test]
+async fn test_auto_routes_with_state() {
+    let router: axum::Router = axum::Router::new()
+        .auto_routes()
+        .with_state("state".to_string());
+
+    let server = TestServer::new(router).unwrap();
+
+    let response = server.get("/state").await;
+    response.assert_status_ok();
+    response.assert_text("state");
+}
+
+#[cfg(feature = "openapi")]
+mod openapi {
+    use axum_controller::{
+        api_route,
+        openapi::{OpenApiRouter, TypedApiRouter},
+    };
+    use axum_test::TestServer;
+
+    /// A documented route.
+    #[api_route(GET "/documented")]
+    async fn documented() -> &'static str {
+        "documented"
+    }
+
+    #[tokio::test]
+    async fn test_auto_api_routes() {
+        let (router, openapi) = OpenApiRouter::<()>::new()
+            .auto_api_routes()
+            .split_for_parts();
+
+        assert!(openapi.paths.paths.contains_key("/documented"));
+        // Controller routes without OpenAPI options are added without documentation
+        assert!(!openapi.paths.paths.contains_key("/items/{id}"));
+
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.get("/documented").await;
+        response.assert_status_ok();
+        response.assert_text("documented");
+
+        let response = server.get("/hello/John").await;
+        response.assert_status_ok();
+
+        let response = server.get("/items/1").await;
+        response.assert_status_ok();
+    }
+}
@@ -0,0 +1,10 @@
+//! Checks the errors reported by the macros for invalid input, and that unusual valid input compiles.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+    #[cfg(feature = "legacy-path-warnings")]
+    t.compile_fail("tests/ui/legacy_path_warnings/*.rs");
+}
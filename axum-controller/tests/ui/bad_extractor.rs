@@ -0,0 +1,11 @@
+#![allow(unused)]
+
+use axum::http::HeaderMap;
+use axum_controller::route;
+
+#[route(POST "/items/{id}")]
+async fn post(id: u32, body: String, headers: HeaderMap) -> String {
+    format!("{id}: {body}")
+}
+
+fn main() {}
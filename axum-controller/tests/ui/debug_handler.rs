@@ -0,0 +1,29 @@
+#![allow(unused)]
+
+use axum::extract::FromRequestParts;
+use axum::http::{request::Parts, StatusCode};
+use axum_controller::route;
+
+#[derive(Clone)]
+struct AppState;
+
+/// An extractor that only works with `AppState`.
+struct CurrentUser;
+
+impl FromRequestParts<AppState> for CurrentUser {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        _state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self)
+    }
+}
+
+#[route(GET "/items/{id}")]
+async fn get(id: u32, user: CurrentUser) -> String {
+    format!("{id}")
+}
+
+fn main() {}
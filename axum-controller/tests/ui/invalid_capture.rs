@@ -0,0 +1,14 @@
+#![allow(unused)]
+
+use axum_controller::route;
+
+#[route(GET "/item/{}")]
+async fn empty() {}
+
+#[route(GET "/item/{*}")]
+async fn empty_wildcard() {}
+
+#[route(GET "/item/{my-id}")]
+async fn not_an_ident(id: u32) {}
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![allow(unused)]
+
+use axum_controller::route;
+
+#[route(GET "/files/*<u32>")]
+async fn wildcard_constraint() {}
+
+#[route(GET r"/files/{*rest<\d+>}")]
+async fn braced_wildcard_constraint(rest: String) {}
+
+#[route(GET r"/legacy/*rest<\d+>")]
+async fn legacy_wildcard_constraint(rest: String) {}
+
+#[route(GET "/pages/{page<..>}")]
+async fn unbounded_range(page: u32) {}
+
+fn main() {}
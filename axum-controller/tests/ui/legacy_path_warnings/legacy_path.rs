@@ -0,0 +1,10 @@
+#![deny(deprecated)]
+
+use axum_controller::route;
+
+#[route(GET "/items/:id/*rest")]
+async fn get(id: u32, rest: String) -> String {
+    format!("{id}/{rest}")
+}
+
+fn main() {}
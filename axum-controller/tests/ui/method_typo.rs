@@ -0,0 +1,20 @@
+#![allow(unused)]
+
+use axum_controller::route;
+
+#[route(GTE "/item")]
+async fn get() {}
+
+#[route(PSOT "/item")]
+async fn post() {}
+
+#[route(Propfind "/dav")]
+async fn propfind() {}
+
+#[route("RE AD" "/read")]
+async fn invalid_literal() {}
+
+#[route("/missing-method")]
+async fn missing_method() {}
+
+fn main() {}
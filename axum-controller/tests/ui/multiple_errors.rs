@@ -0,0 +1,16 @@
+#![allow(unused)]
+
+use axum_controller::{controller, route};
+
+struct Controller;
+
+#[controller(path = "/items", midleware = (), stat = ())]
+impl Controller {
+    #[route(GET "/?pgae&size")]
+    async fn list(page: u32, size: u32) {}
+
+    #[route(GET "/{id}?verbose")]
+    async fn get(#[path] item_id: u32, verbose: bool) {}
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+//! Handlers that `debug_handler` doesn't support, which must compile without it.
+
+use axum::extract::{FromRef, State};
+use axum_controller::{controller, route, TypedRouter};
+
+#[route(GET "/generic/{id}")]
+async fn generic<T: std::fmt::Display + serde::de::DeserializeOwned + Send + 'static>(
+    id: T,
+) -> String {
+    id.to_string()
+}
+
+#[derive(Clone)]
+struct Greeter {
+    greeting: String,
+}
+
+impl FromRef<()> for Greeter {
+    fn from_ref(_state: &()) -> Self {
+        Self {
+            greeting: "Hello".to_string(),
+        }
+    }
+}
+
+#[controller(path = "/greet")]
+impl Greeter {
+    #[route(GET "/{name}")]
+    async fn greet(&self, name: String) -> String {
+        format!("{}, {name}!", self.greeting)
+    }
+}
+
+struct Wrapper<T>(T);
+
+#[controller(path = "/wrapper")]
+impl<T: Send + Sync + 'static> Wrapper<T> {
+    #[route(GET "/")]
+    async fn index(State(_state): State<()>) -> &'static str {
+        "index"
+    }
+}
+
+fn main() {
+    let _router: axum::Router = axum::Router::new()
+        .typed_route(generic::<u32>)
+        .merge(Greeter::into_router())
+        .merge(Wrapper::<u8>::into_router());
+}
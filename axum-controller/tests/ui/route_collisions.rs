@@ -0,0 +1,27 @@
+#![allow(unused)]
+
+use axum_controller::controller;
+
+struct CaptureNames;
+
+#[controller(path = "/items")]
+impl CaptureNames {
+    #[route(GET "/{a}")]
+    async fn get(a: u32) {}
+
+    #[route(DELETE "/{b}")]
+    async fn delete(b: u32) {}
+}
+
+struct OverlappingMethods;
+
+#[controller(path = "/items")]
+impl OverlappingMethods {
+    #[route(GET | HEAD "/{id}")]
+    async fn get(id: u32) {}
+
+    #[route(HEAD "/{id}")]
+    async fn head(id: u32) {}
+}
+
+fn main() {}
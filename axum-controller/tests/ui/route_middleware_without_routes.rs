@@ -0,0 +1,19 @@
+#![allow(unused)]
+
+use axum_controller::controller;
+
+struct EmptyController;
+
+#[controller(path = "/empty", route_middleware = axum::middleware::from_fn(pass))]
+impl EmptyController {
+    fn helper() {}
+}
+
+async fn pass(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    next.run(request).await
+}
+
+fn main() {}